@@ -0,0 +1,85 @@
+/*
+ * MegaSena Monitor - Minimalist desktop application for managing bets.
+ * Copyright (C) 2025 Zander Cattapreta
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Benchmark de `Database::listar_apostas` com 10 mil apostas Teimosinha
+//
+//     cargo bench --bench listar_apostas
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use megasena_app_lib::database::Database;
+use megasena_app_lib::domain::{Concurso, Dezenas, QuantidadeConcursos};
+use megasena_app_lib::models::Resultado;
+use std::path::PathBuf;
+
+const APOSTAS: i32 = 10_000;
+const CONCURSOS: i32 = 12;
+const PRIMEIRO_CONCURSO: i32 = 2800;
+
+/// Banco em memória com apostas de 6 a 15 números cobrindo 12 concursos já sorteados
+fn popular_banco() -> Database {
+    let mut db = Database::new(PathBuf::from(":memory:")).unwrap();
+    db.init().unwrap();
+
+    for i in 0..APOSTAS {
+        let quantidade = 6 + (i % 10);
+        let numeros: Vec<i32> = (0..quantidade).map(|n| (i + n * 7) % 60 + 1).collect();
+        db.adicionar_aposta(
+            &Dezenas::novas(numeros).unwrap(),
+            Concurso::novo(PRIMEIRO_CONCURSO).unwrap(),
+            QuantidadeConcursos::nova(CONCURSOS).unwrap(),
+        )
+        .unwrap();
+    }
+
+    for concurso in PRIMEIRO_CONCURSO..PRIMEIRO_CONCURSO + CONCURSOS {
+        let numeros_sorteados: Vec<i32> = (0..6).map(|n| (concurso + n * 11) % 60 + 1).collect();
+        db.salvar_resultado(&Resultado {
+            concurso,
+            numeros_sorteados,
+            data_sorteio: "01/01/2025".to_string(),
+            acumulado: false,
+            valor_premio: None,
+            ganhadores: None,
+            valor_total: None,
+            faixas: Vec::new(),
+            fonte: None,
+            fetched_at: None,
+            provisional: false,
+            payload: None,
+        })
+        .unwrap();
+        db.processar_acertos_concurso(concurso).unwrap();
+    }
+
+    db
+}
+
+fn bench_listar_apostas(c: &mut Criterion) {
+    let db = popular_banco();
+    assert_eq!(db.listar_apostas().unwrap().len(), APOSTAS as usize);
+
+    let mut group = c.benchmark_group("listar_apostas");
+    group.sample_size(10);
+    group.bench_function("10k_apostas_12_concursos", |b| {
+        b.iter(|| db.listar_apostas().unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_listar_apostas);
+criterion_main!(benches);
@@ -0,0 +1,172 @@
+/*
+ * MegaSena Monitor - Minimalist desktop application for managing bets.
+ * Copyright (C) 2025 Zander Cattapreta
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Background check scheduler driven by the draw calendar
+
+use crate::calendario;
+use chrono::{DateTime, Duration, Timelike, Utc};
+use chrono_tz::America::Sao_Paulo;
+use std::sync::{Condvar, Mutex};
+
+/// Fim da janela pós-sorteio, no horário de Brasília (o início é `HORA_SORTEIO`)
+pub const HORA_FIM_JANELA: u32 = 23;
+
+/// Intervalo entre verificações durante a janela pós-sorteio
+pub const INTERVALO_JANELA_MIN: i64 = 10;
+
+/// Intervalo máximo entre verificações fora da janela
+pub const INTERVALO_FORA_JANELA_MIN: i64 = 180;
+
+/// Indica se `agora` está entre o sorteio e o fim da janela em que o
+/// resultado costuma ser publicado
+pub fn em_janela_pos_sorteio(agora: DateTime<Utc>) -> bool {
+    let local = agora.with_timezone(&Sao_Paulo);
+    calendario::eh_dia_de_sorteio(local.date_naive())
+        && (calendario::HORA_SORTEIO..HORA_FIM_JANELA).contains(&local.hour())
+}
+
+/// Momento da próxima verificação: a cada poucos minutos na janela
+/// pós-sorteio; fora dela, com intervalo maior, mas nunca depois do
+/// início da próxima janela
+pub fn proxima_verificacao(agora: DateTime<Utc>) -> DateTime<Utc> {
+    if em_janela_pos_sorteio(agora) {
+        return agora + Duration::minutes(INTERVALO_JANELA_MIN);
+    }
+    let proximo_sorteio = calendario::proximo_sorteio(agora).with_timezone(&Utc);
+    (agora + Duration::minutes(INTERVALO_FORA_JANELA_MIN)).min(proximo_sorteio)
+}
+
+#[derive(Default)]
+struct Estado {
+    proxima: Option<DateTime<Utc>>,
+    verificar_agora: bool,
+}
+
+/// Agenda das verificações em segundo plano. A thread do agendador dorme
+/// até a próxima verificação ou até alguém pedir `verificar_agora`.
+#[derive(Default)]
+pub struct Agendador {
+    estado: Mutex<Estado>,
+    sinal: Condvar,
+}
+
+impl Agendador {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Próxima verificação agendada (`None` enquanto uma verificação roda)
+    pub fn proxima(&self) -> Option<DateTime<Utc>> {
+        self.estado.lock().ok().and_then(|estado| estado.proxima)
+    }
+
+    /// Antecipa a próxima verificação para já
+    pub fn verificar_agora(&self) {
+        if let Ok(mut estado) = self.estado.lock() {
+            estado.verificar_agora = true;
+            self.sinal.notify_all();
+        }
+    }
+
+    /// Bloqueia até `proxima` ou até um pedido de `verificar_agora`
+    pub fn aguardar(&self, proxima: DateTime<Utc>) {
+        let Ok(mut estado) = self.estado.lock() else {
+            return;
+        };
+        estado.proxima = Some(proxima);
+        while !estado.verificar_agora {
+            let restante = match (proxima - Utc::now()).to_std() {
+                Ok(restante) if !restante.is_zero() => restante,
+                _ => break,
+            };
+            estado = match self.sinal.wait_timeout(estado, restante) {
+                Ok((estado, _)) => estado,
+                Err(_) => return,
+            };
+        }
+        estado.verificar_agora = false;
+        estado.proxima = None;
+    }
+
+    /// Executa `verificar` imediatamente e, depois, sempre no horário
+    /// definido por `proxima_verificacao`. Não retorna.
+    pub fn executar(&self, mut verificar: impl FnMut()) -> ! {
+        loop {
+            verificar();
+            let proxima = proxima_verificacao(Utc::now());
+            println!(
+                "[Agendador] Próxima verificação em {}",
+                proxima.with_timezone(&Sao_Paulo).format("%d/%m/%Y %H:%M")
+            );
+            self.aguardar(proxima);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::sync::Arc;
+
+    fn em_sao_paulo(dia: u32, hora: u32, minuto: u32) -> DateTime<Utc> {
+        Sao_Paulo
+            .with_ymd_and_hms(2026, 1, dia, hora, minuto, 0)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_janela_pos_sorteio_verifica_com_frequencia() {
+        // Sábado, 03/01, logo após o sorteio
+        let agora = em_sao_paulo(3, 20, 30);
+        assert!(em_janela_pos_sorteio(agora));
+        assert_eq!(proxima_verificacao(agora), em_sao_paulo(3, 20, 40));
+
+        // Passada a janela, espera o intervalo longo
+        let agora = em_sao_paulo(3, 23, 30);
+        assert!(!em_janela_pos_sorteio(agora));
+        assert_eq!(proxima_verificacao(agora), em_sao_paulo(4, 2, 30));
+    }
+
+    #[test]
+    fn test_fora_da_janela_nao_perde_o_sorteio() {
+        // Terça, 06/01, às 18h30: a próxima verificação é o início da janela
+        assert_eq!(
+            proxima_verificacao(em_sao_paulo(6, 18, 30)),
+            em_sao_paulo(6, 20, 0)
+        );
+        // Domingo não tem sorteio
+        assert!(!em_janela_pos_sorteio(em_sao_paulo(4, 21, 0)));
+    }
+
+    #[test]
+    fn test_verificar_agora_acorda_o_agendador() {
+        let agendador = Arc::new(Agendador::new());
+        let em_espera = agendador.clone();
+        let espera =
+            std::thread::spawn(move || em_espera.aguardar(Utc::now() + Duration::hours(1)));
+
+        while agendador.proxima().is_none() {
+            std::thread::yield_now();
+        }
+        agendador.verificar_agora();
+        espera.join().unwrap();
+        assert_eq!(agendador.proxima(), None);
+    }
+}
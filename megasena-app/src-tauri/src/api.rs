@@ -18,12 +18,26 @@
 
 // API module for fetching Mega-Sena results
 
-use crate::models::Resultado;
+use crate::calendario;
+use crate::config::{
+    default_max_concurrent, ApiConfig, CircuitBreakerConfig, ProviderConfig, RetryConfig,
+};
+use crate::error::MegaSenaError;
+use crate::models::{
+    DiagnosticoProvedor, FaixaPremio, ProximoConcurso, Resultado, StatusVerificacao,
+    VerificacaoResultado,
+};
+use crate::resiliencia::{atraso_backoff, CircuitBreaker};
 use reqwest::blocking::Client;
 use serde::Deserialize;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
 
 #[derive(Debug, Deserialize)]
 struct Rateio {
+    faixa: Option<i32>,
     #[serde(rename = "numeroDeGanhadores")]
     ganhadores: i32,
     #[serde(rename = "valorPremio")]
@@ -46,34 +60,147 @@ struct CaixaApiResponse {
     valor_estimado_proximo: Option<f64>,
     #[serde(rename = "valorAcumuladoProximoConcurso")]
     valor_acumulado_proximo: Option<f64>,
+    #[serde(rename = "numeroConcursoProximo")]
+    numero_proximo: Option<i32>,
+    #[serde(rename = "dataProximoConcurso")]
+    data_proximo: Option<String>,
 }
 
-/// Busca resultado da API oficial da Caixa
-fn fetch_caixa_api(concurso: i32) -> Result<Resultado, String> {
-    let url = format!(
-        "https://servicebus2.caixa.gov.br/portaldeloterias/api/megasena/{}",
-        concurso
-    );
+const USER_AGENT_NAVEGADOR: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Safari/537.36";
 
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Safari/537.36")
+/// Falha ao consultar um provedor. Só as transitórias são repetidas; todas,
+/// exceto `NaoEncontrado`, contam para o disjuntor do provedor.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErroBusca {
+    /// O provedor respondeu, mas ainda não tem o concurso
+    NaoEncontrado,
+    /// Timeout ou falha de conexão
+    Transitorio(String),
+    /// Status HTTP de erro (5xx e 429 são transitórios)
+    Http(u16),
+    /// Resposta fora do formato esperado
+    Invalido(String),
+    /// Provedor pulado pelo disjuntor
+    CircuitoAberto,
+}
+
+impl ErroBusca {
+    /// Vale a pena repetir a chamada
+    pub fn transitorio(&self) -> bool {
+        match self {
+            ErroBusca::Transitorio(_) => true,
+            ErroBusca::Http(status) => *status >= 500 || *status == 429,
+            _ => false,
+        }
+    }
+
+    /// Converte a falha de um provedor no erro da aplicação
+    fn para_erro(&self, provedor: &str, concurso: Option<i32>) -> MegaSenaError {
+        match self {
+            ErroBusca::NaoEncontrado => match concurso {
+                Some(concurso) => MegaSenaError::NaoSorteado { concurso },
+                None => MegaSenaError::NaoEncontrado(format!(
+                    "O provedor '{}' não informou o último concurso",
+                    provedor
+                )),
+            },
+            ErroBusca::Transitorio(causa) => {
+                MegaSenaError::Rede(format!("{}: {}", provedor, causa))
+            }
+            ErroBusca::Http(status) => MegaSenaError::StatusHttp {
+                provedor: provedor.to_string(),
+                status: *status,
+            },
+            ErroBusca::Invalido(causa) => {
+                MegaSenaError::Formato(format!("{}: {}", provedor, causa))
+            }
+            ErroBusca::CircuitoAberto => MegaSenaError::Rede(format!("{}: {}", provedor, self)),
+        }
+    }
+}
+
+impl fmt::Display for ErroBusca {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErroBusca::NaoEncontrado => write!(f, "concurso não encontrado"),
+            ErroBusca::Transitorio(msg) | ErroBusca::Invalido(msg) => write!(f, "{}", msg),
+            ErroBusca::Http(status) => write!(f, "API retornou status: {}", status),
+            ErroBusca::CircuitoAberto => write!(f, "provedor temporariamente desativado"),
+        }
+    }
+}
+
+/// Erro da cadeia a partir das falhas de cada provedor, em ordem de
+/// prioridade. Se algum provedor respondeu que não tem o concurso, ele
+/// ainda não foi publicado; senão vale a falha do provedor principal.
+fn erro_da_cadeia(concurso: Option<i32>, falhas: &[(&str, ErroBusca)]) -> MegaSenaError {
+    if let Some((provedor, erro)) = falhas
+        .iter()
+        .find(|(_, erro)| *erro == ErroBusca::NaoEncontrado)
+    {
+        return erro.para_erro(provedor, concurso);
+    }
+    match falhas.first() {
+        Some((provedor, erro)) => erro.para_erro(provedor, concurso),
+        None => MegaSenaError::Rede("nenhum provedor de resultados habilitado".to_string()),
+    }
+}
+
+/// Fonte de resultados da Mega-Sena
+pub trait ResultProvider: Send + Sync {
+    /// Identificador usado na configuração e nos logs
+    fn name(&self) -> &str;
+
+    /// Ordem na cadeia de provedores (menor valor = consultado primeiro)
+    fn priority(&self) -> i32;
+
+    /// Busca o resultado de um concurso específico
+    fn fetch(&self, concurso: i32) -> Result<Resultado, ErroBusca>;
+
+    /// Busca o resultado mais recente publicado pela fonte
+    fn latest(&self) -> Result<Resultado, ErroBusca>;
+
+    /// Fonte oficial (Caixa). Resultados de outras fontes ficam marcados
+    /// como provisórios até serem conferidos com uma fonte oficial.
+    fn official(&self) -> bool {
+        false
+    }
+}
+
+fn criar_cliente(timeout_secs: u64, user_agent: &str) -> Result<Client, String> {
+    Client::builder()
+        .timeout(Duration::from_secs(timeout_secs))
+        .user_agent(user_agent)
         .build()
-        .map_err(|e| format!("Erro ao criar cliente HTTP: {}", e))?;
+        .map_err(|e| format!("Erro ao criar cliente HTTP: {}", e))
+}
 
-    let response = client
-        .get(&url)
-        .send()
-        .map_err(|e| format!("Erro ao fazer requisição: {}", e))?;
+/// Identifica a faixa do rateio: campo `faixa` quando presente,
+/// senão o número de acertos no início de `descricaoFaixa` ("5 acertos")
+fn converter_faixa(rateio: &Rateio) -> Option<FaixaPremio> {
+    let acertos = match rateio.faixa {
+        Some(faixa) => 7 - faixa,
+        None => rateio
+            .descricao
+            .split_whitespace()
+            .next()
+            .and_then(|s| s.parse::<i32>().ok())?,
+    };
 
-    if !response.status().is_success() {
-        return Err(format!("API retornou status: {}", response.status()));
+    if !(4..=6).contains(&acertos) {
+        return None;
     }
 
-    let data: CaixaApiResponse = response
-        .json()
-        .map_err(|e| format!("Erro ao parsear JSON: {}", e))?;
+    Some(FaixaPremio {
+        faixa: 7 - acertos,
+        acertos,
+        ganhadores: rateio.ganhadores,
+        valor_premio: rateio.valor.unwrap_or(0.0),
+    })
+}
 
+/// Converte a resposta no formato da Caixa (também usado pela API Guidi)
+fn converter_resposta(data: CaixaApiResponse) -> Result<Resultado, String> {
     // Converter strings "01", "02" para números
     let numeros_sorteados: Vec<i32> = data
         .dezenas
@@ -88,10 +215,18 @@ fn fetch_caixa_api(concurso: i32) -> Result<Resultado, String> {
         ));
     }
 
-    // Extrair prêmio do rank 1 (Sena)
-    let sena_info = data.lista_rateio.iter().find(|r| r.descricao.to_lowercase().contains("6"));
+    let mut faixas: Vec<FaixaPremio> = data
+        .lista_rateio
+        .iter()
+        .filter_map(converter_faixa)
+        .collect();
+    faixas.sort_by_key(|f| f.faixa);
+    faixas.dedup_by_key(|f| f.faixa);
+
+    // Extrair prêmio da faixa 1 (Sena)
+    let sena_info = faixas.iter().find(|f| f.faixa == 1);
     let ganhadores = sena_info.map(|s| s.ganhadores);
-    let valor_premio = sena_info.and_then(|s| s.valor);
+    let valor_premio = sena_info.map(|s| s.valor_premio);
 
     // Calcular Valor Total
     let valor_total = if let (Some(g), Some(v)) = (ganhadores, valor_premio) {
@@ -113,162 +248,1096 @@ fn fetch_caixa_api(concurso: i32) -> Result<Resultado, String> {
         valor_premio,
         ganhadores,
         valor_total,
+        faixas,
+        fonte: None,
+        fetched_at: None,
+        provisional: false,
+        payload: None,
     })
 }
 
-/// Busca resultado de APIs alternativas (fallback)
-fn fetch_external_fallback(concurso: i32) -> Result<Resultado, String> {
-    // Fonte 1: API Guidi (Open Source)
-    let url = format!(
-        "https://api.guidi.dev.br/loteria/megasena/{}",
-        concurso
-    );
+/// GET em uma URL que responde no formato da Caixa
+fn buscar_resultado(client: &Client, url: &str) -> Result<Resultado, ErroBusca> {
+    let response = client
+        .get(url)
+        .send()
+        .map_err(|e| ErroBusca::Transitorio(format!("Erro ao fazer requisição: {}", e)))?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Err(ErroBusca::NaoEncontrado);
+    }
+    if !status.is_success() {
+        return Err(ErroBusca::Http(status.as_u16()));
+    }
 
-    println!("Tentando fallback Fonte 1 (Guidi): {}", url);
+    let corpo = response
+        .text()
+        .map_err(|e| ErroBusca::Transitorio(format!("Erro ao ler resposta: {}", e)))?;
 
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .user_agent("MegaSena Monitor/1.0.0")
-        .build()
-        .map_err(|e| format!("Erro ao criar cliente HTTP Fallback: {}", e))?;
-
-    match client.get(&url).send() {
-        Ok(response) => {
-            let status = response.status();
-            if status.is_success() {
-                if let Ok(data) = response.json::<CaixaApiResponse>() {
-                    // Reutilizar o parsing da Caixa já que o formato é idêntico
-                     let numeros_sorteados: Vec<i32> = data
-                        .dezenas
-                        .iter()
-                        .filter_map(|s| s.parse::<i32>().ok())
-                        .collect();
-
-                    if numeros_sorteados.len() == 6 {
-                        let sena_info = data.lista_rateio.iter().find(|r| r.descricao.to_lowercase().contains("6"));
-                        let ganhadores = sena_info.map(|s| s.ganhadores);
-                        let valor_premio = sena_info.and_then(|s| s.valor);
-
-                        let valor_total = if let (Some(g), Some(v)) = (ganhadores, valor_premio) {
-                            if g > 0 { Some(g as f64 * v) }
-                            else { data.valor_acumulado_proximo.or(data.valor_estimado_proximo) }
-                        } else {
-                            data.valor_estimado_proximo
-                        };
-
-                        return Ok(Resultado {
-                            concurso: data.numero,
-                            numeros_sorteados,
-                            data_sorteio: data.data_apuracao,
-                            acumulado: data.acumulado,
-                            valor_premio,
-                            ganhadores,
-                            valor_total,
-                        });
-                    }
+    let mut resultado = interpretar_payload(&corpo).map_err(ErroBusca::Invalido)?;
+    resultado.payload = Some(corpo);
+    Ok(resultado)
+}
+
+/// Interpreta um corpo JSON no formato da Caixa (também usado pela API Guidi).
+/// Usado tanto nas buscas quanto no reprocessamento de `resultados_raw`.
+pub fn interpretar_payload(corpo: &str) -> Result<Resultado, String> {
+    let data: CaixaApiResponse =
+        serde_json::from_str(corpo).map_err(|e| format!("Erro ao parsear JSON: {}", e))?;
+    converter_resposta(data)
+}
+
+/// Próximo sorteio segundo a resposta bruta de `resultado`. Sem ela (ex.:
+/// resultado importado da planilha), só o número do concurso é conhecido.
+pub fn proximo_concurso(resultado: &Resultado) -> ProximoConcurso {
+    let data = resultado
+        .payload
+        .as_deref()
+        .and_then(|corpo| serde_json::from_str::<CaixaApiResponse>(corpo).ok());
+
+    match data {
+        Some(data) => ProximoConcurso {
+            concurso: data.numero_proximo.unwrap_or(resultado.concurso + 1),
+            data_sorteio: data.data_proximo.filter(|d| !d.is_empty()),
+            valor_estimado: data.valor_estimado_proximo,
+            valor_acumulado: data.valor_acumulado_proximo,
+        },
+        None => ProximoConcurso {
+            concurso: resultado.concurso + 1,
+            data_sorteio: None,
+            valor_estimado: None,
+            valor_acumulado: None,
+        },
+    }
+}
+
+/// API oficial da Caixa
+pub struct CaixaProvider {
+    client: Client,
+    priority: i32,
+    base_url: String,
+}
+
+impl CaixaProvider {
+    const BASE_URL: &'static str = "https://servicebus2.caixa.gov.br/portaldeloterias/api/megasena";
+
+    pub fn new(priority: i32, timeout_secs: u64) -> Result<Self, String> {
+        Ok(CaixaProvider {
+            client: criar_cliente(timeout_secs, USER_AGENT_NAVEGADOR)?,
+            priority,
+            base_url: Self::BASE_URL.to_string(),
+        })
+    }
+
+    /// Troca o endereço da API (ex.: `fake_loteria_server` em desenvolvimento)
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+}
+
+impl ResultProvider for CaixaProvider {
+    fn name(&self) -> &str {
+        "caixa"
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn fetch(&self, concurso: i32) -> Result<Resultado, ErroBusca> {
+        buscar_resultado(&self.client, &format!("{}/{}", self.base_url, concurso))
+    }
+
+    fn latest(&self) -> Result<Resultado, ErroBusca> {
+        buscar_resultado(&self.client, &format!("{}/", self.base_url))
+    }
+
+    fn official(&self) -> bool {
+        true
+    }
+}
+
+/// API Guidi (Open Source), que replica o formato da Caixa
+pub struct GuidiProvider {
+    client: Client,
+    priority: i32,
+    base_url: String,
+}
+
+impl GuidiProvider {
+    const BASE_URL: &'static str = "https://api.guidi.dev.br/loteria/megasena";
+
+    pub fn new(priority: i32, timeout_secs: u64) -> Result<Self, String> {
+        Ok(GuidiProvider {
+            client: criar_cliente(timeout_secs, "MegaSena Monitor/1.0.0")?,
+            priority,
+            base_url: Self::BASE_URL.to_string(),
+        })
+    }
+
+    /// Troca o endereço da API (ex.: `fake_loteria_server` em desenvolvimento)
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+}
+
+impl ResultProvider for GuidiProvider {
+    fn name(&self) -> &str {
+        "guidi"
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn fetch(&self, concurso: i32) -> Result<Resultado, ErroBusca> {
+        buscar_resultado(&self.client, &format!("{}/{}", self.base_url, concurso))
+    }
+
+    fn latest(&self) -> Result<Resultado, ErroBusca> {
+        buscar_resultado(&self.client, &format!("{}/ultimo", self.base_url))
+    }
+}
+
+fn criar_provedor(config: &ProviderConfig) -> Result<Box<dyn ResultProvider>, String> {
+    let base_url = config.base_url.as_deref();
+    match config.name.as_str() {
+        "caixa" => {
+            let provider = CaixaProvider::new(config.priority, config.timeout_secs)?;
+            Ok(Box::new(match base_url {
+                Some(url) => provider.with_base_url(url),
+                None => provider,
+            }))
+        }
+        "guidi" => {
+            let provider = GuidiProvider::new(config.priority, config.timeout_secs)?;
+            Ok(Box::new(match base_url {
+                Some(url) => provider.with_base_url(url),
+                None => provider,
+            }))
+        }
+        outro => Err(format!("Provedor desconhecido: {}", outro)),
+    }
+}
+
+/// Concursos além do último publicado que podem ser consultados quando o
+/// calendário indica que já foram sorteados
+const SONDAGEM_MAXIMA: i32 = 3;
+
+/// Cadeia de provedores consultados em ordem de prioridade, cada um com
+/// seu disjuntor. Clonar é barato: provedores e disjuntores são
+/// compartilhados entre as cópias.
+///
+/// As consultas bloqueiam a thread durante as requisições e as esperas entre
+/// tentativas. Comandos e tarefas assíncronas devem chamá-las por
+/// `tokio::task::spawn_blocking`, nunca na thread principal.
+#[derive(Clone)]
+pub struct ProviderChain {
+    providers: Arc<[Box<dyn ResultProvider>]>,
+    breakers: Arc<[CircuitBreaker]>,
+    max_concurrent: usize,
+    retry: RetryConfig,
+    cross_validate: bool,
+}
+
+/// Resultado obtido pela cadeia, com a verificação cruzada quando ativada
+#[derive(Debug, Clone)]
+pub struct ResultadoBuscado {
+    pub resultado: Resultado,
+    pub verificacao: Option<VerificacaoResultado>,
+}
+
+/// Busca de um concurso disparada por `ProviderChain::iniciar_buscas`
+pub struct BuscaEmAndamento(tokio::task::JoinHandle<Result<ResultadoBuscado, MegaSenaError>>);
+
+impl BuscaEmAndamento {
+    pub async fn concluir(self) -> Result<ResultadoBuscado, MegaSenaError> {
+        self.0
+            .await
+            .unwrap_or_else(|e| Err(MegaSenaError::Rede(e.to_string())))
+    }
+}
+
+impl ProviderChain {
+    pub fn new(mut providers: Vec<Box<dyn ResultProvider>>) -> Self {
+        providers.sort_by_key(|p| p.priority());
+        ProviderChain {
+            providers: providers.into(),
+            breakers: Arc::new([]),
+            max_concurrent: default_max_concurrent(),
+            retry: RetryConfig::default(),
+            cross_validate: false,
+        }
+        .with_circuit_breaker(CircuitBreakerConfig::default())
+    }
+
+    /// Limite de requisições simultâneas em `buscar_resultados`
+    pub fn with_max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent = max_concurrent.max(1);
+        self
+    }
+
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Consultar todos os provedores e comparar as respostas em `buscar_verificado`
+    pub fn with_cross_validation(mut self, cross_validate: bool) -> Self {
+        self.cross_validate = cross_validate;
+        self
+    }
+
+    /// Substitui os disjuntores, zerando o histórico de falhas
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.breakers = self
+            .providers
+            .iter()
+            .map(|_| CircuitBreaker::new(config.clone()))
+            .collect();
+        self
+    }
+
+    /// Monta a cadeia com os provedores habilitados na configuração
+    pub fn from_config(config: &ApiConfig) -> Self {
+        let providers = config
+            .providers
+            .iter()
+            .filter(|p| p.enabled)
+            .filter_map(|p| match criar_provedor(p) {
+                Ok(provider) => Some(provider),
+                Err(e) => {
+                    eprintln!("Provedor '{}' ignorado: {}", p.name, e);
+                    None
+                }
+            })
+            .collect();
+
+        ProviderChain::new(providers)
+            .with_max_concurrent(config.max_concurrent_requests)
+            .with_retry(config.retry.clone())
+            .with_circuit_breaker(config.circuit_breaker.clone())
+            .with_cross_validation(config.cross_validate)
+    }
+
+    pub fn provider_names(&self) -> Vec<&str> {
+        self.providers.iter().map(|p| p.name()).collect()
+    }
+
+    /// Estado do disjuntor de cada provedor, na ordem da cadeia
+    pub fn diagnostico(&self) -> Vec<DiagnosticoProvedor> {
+        self.providers
+            .iter()
+            .zip(self.breakers.iter())
+            .map(|(provider, breaker)| {
+                let situacao = breaker.situacao();
+                DiagnosticoProvedor {
+                    nome: provider.name().to_string(),
+                    prioridade: provider.priority(),
+                    estado: situacao.estado,
+                    falhas_consecutivas: situacao.falhas_consecutivas,
+                    total_sucessos: situacao.total_sucessos,
+                    total_falhas: situacao.total_falhas,
+                    ultimo_erro: situacao.ultimo_erro,
+                    reabre_em_segundos: situacao.reabre_em.map(|d| d.as_secs().max(1)),
+                }
+            })
+            .collect()
+    }
+
+    /// Executa `operacao` no provedor de índice `indice` respeitando o
+    /// disjuntor e repetindo falhas transitórias com backoff. A espera entre
+    /// tentativas dorme na thread atual.
+    fn chamar(
+        &self,
+        indice: usize,
+        operacao: impl Fn(&dyn ResultProvider) -> Result<Resultado, ErroBusca>,
+    ) -> Result<Resultado, ErroBusca> {
+        let provider = self.providers[indice].as_ref();
+        let breaker = &self.breakers[indice];
+        if !breaker.permitir() {
+            return Err(ErroBusca::CircuitoAberto);
+        }
+
+        let mut tentativa = 1;
+        loop {
+            match operacao(provider) {
+                Ok(mut resultado) => {
+                    breaker.registrar_sucesso();
+                    resultado.fonte = Some(provider.name().to_string());
+                    resultado.fetched_at =
+                        Some(chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string());
+                    resultado.provisional = !provider.official();
+                    return Ok(resultado);
+                }
+                // O provedor respondeu: está saudável, só não tem o concurso
+                Err(ErroBusca::NaoEncontrado) => {
+                    breaker.registrar_sucesso();
+                    return Err(ErroBusca::NaoEncontrado);
+                }
+                Err(e) if e.transitorio() && tentativa < self.retry.max_attempts => {
+                    let espera = atraso_backoff(&self.retry, tentativa);
+                    eprintln!(
+                        "Provedor '{}' falhou ({}), tentativa {} de {} em {:?}",
+                        provider.name(),
+                        e,
+                        tentativa + 1,
+                        self.retry.max_attempts,
+                        espera
+                    );
+                    std::thread::sleep(espera);
+                    tentativa += 1;
+                }
+                Err(e) => {
+                    breaker.registrar_falha(&e.to_string());
+                    return Err(e);
                 }
             }
-            Err(format!("Fallback Fonte 1 retornou status: {}", status))
         }
-        Err(e) => Err(format!("Erro na requisição de fallback: {}", e))
     }
-}
 
-/// Busca o número do último concurso realizado com fallback
-/// Busca o número do último concurso com estratégia de Exploração de Fronteira
-pub fn obter_ultimo_concurso_numero() -> Result<i32, String> {
-    let url = "https://servicebus2.caixa.gov.br/portaldeloterias/api/megasena/";
+    /// Resultado mais recente do primeiro provedor que responder
+    pub fn buscar_ultimo(&self) -> Result<Resultado, MegaSenaError> {
+        let mut falhas = Vec::new();
+        for (i, provider) in self.providers.iter().enumerate() {
+            match self.chamar(i, |p| p.latest()) {
+                Ok(resultado) => return Ok(resultado),
+                Err(e) => {
+                    eprintln!("Provedor '{}' sem último concurso: {}", provider.name(), e);
+                    falhas.push((provider.name(), e));
+                }
+            }
+        }
+        Err(erro_da_cadeia(None, &falhas))
+    }
 
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Safari/537.36")
-        .build()
-        .map_err(|e| format!("Erro ao criar cliente HTTP: {}", e))?;
-
-    // 1. Obter Âncora Oficial
-    let mut anchor = match client.get(url).send() {
-        Ok(response) => {
-            if response.status().is_success() {
-                if let Ok(data) = response.json::<CaixaApiResponse>() {
-                    data.numero
-                } else { 0 }
-            } else { 0 }
-        }
-        Err(_) => 0,
-    };
+    /// Número do último concurso sorteado. O calendário de sorteios estima
+    /// quantos concursos houve desde a referência mais recente (último
+    /// publicado pelos provedores, `cache` ou tabela de especiais). Só os
+    /// concursos que o calendário prevê além do publicado são consultados,
+    /// cobrindo o atraso das APIs em atualizar o "último" (ex.: Mega da
+    /// Virada). Sem resposta dos provedores, vale a estimativa.
+    pub fn obter_ultimo_concurso_numero(
+        &self,
+        cache: Option<&Resultado>,
+    ) -> Result<i32, MegaSenaError> {
+        self.obter_ultimo_concurso_numero_em(cache, chrono::Utc::now())
+    }
+
+    /// Como `obter_ultimo_concurso_numero`, estimando pelo calendário em `agora`
+    pub fn obter_ultimo_concurso_numero_em(
+        &self,
+        cache: Option<&Resultado>,
+        agora: chrono::DateTime<chrono::Utc>,
+    ) -> Result<i32, MegaSenaError> {
+        let referencia_cache =
+            cache.and_then(|r| calendario::Referencia::nova(r.concurso, &r.data_sorteio));
 
-    // 2. Se Caixa falhou, tenta Anchor via Fallback
-    if anchor == 0 {
-        let fallback_url = "https://api.guidi.dev.br/loteria/megasena/ultimo";
-        anchor = match client.get(fallback_url).send() {
-            Ok(response) => {
-                if response.status().is_success() {
-                    if let Ok(data) = response.json::<CaixaApiResponse>() {
-                        data.numero
-                    } else { 2954 }
-                } else { 2954 }
+        let publicado = match self.buscar_ultimo() {
+            Ok(publicado) => publicado,
+            Err(e) => {
+                let referencia = calendario::melhor_referencia(referencia_cache);
+                let estimado = calendario::estimar_concurso_atual(referencia, agora);
+                eprintln!(
+                    "{}; concurso {} estimado pelo calendário a partir do {}",
+                    e, estimado, referencia.concurso
+                );
+                return Ok(estimado);
             }
-            Err(_) => 2954,
         };
+
+        let referencia_publicada =
+            calendario::Referencia::nova(publicado.concurso, &publicado.data_sorteio);
+        let referencia =
+            calendario::melhor_referencia(referencia_publicada.into_iter().chain(referencia_cache));
+        let estimado = calendario::estimar_concurso_atual(referencia, agora);
+
+        let mut ultimo = publicado.concurso.max(referencia.concurso);
+        let limite = estimado.min(ultimo + SONDAGEM_MAXIMA);
+        while ultimo < limite {
+            match self.verificar_resultado(ultimo + 1) {
+                Ok(_) => {
+                    println!("Concurso {} publicado antes do índice da API", ultimo + 1);
+                    ultimo += 1;
+                }
+                Err(_) => break,
+            }
+        }
+
+        Ok(ultimo)
     }
 
-    // 3. EXPLORAÇÃO DE FRONTEIRA: Tenta descobrir se o próximo ou o seguinte já existem
-    // Isso é crucial para dias como hoje (Mega da Virada), onde o sistema principal demora a atualizar a âncora.
+    pub fn verificar_resultado(&self, concurso: i32) -> Result<Resultado, MegaSenaError> {
+        let mut falhas = Vec::new();
+        for (i, provider) in self.providers.iter().enumerate() {
+            println!(
+                "Tentando provedor '{}' para concurso {}",
+                provider.name(),
+                concurso
+            );
 
-    // 3. EXPLORAÇÃO DE FRONTEIRA
-    // Tenta descobrir concursos à frente da âncora oficial
-    if let Ok(res) = verificar_resultado(anchor + 1) {
-        println!("DESCOBERTA: Concurso {} detectado antecipadamente!", anchor + 1);
-        anchor = res.concurso;
-        
-        if let Ok(res2) = verificar_resultado(anchor + 1) {
-            println!("DESCOBERTA EXTRAORDINÁRIA: Concurso {} detectado!", res2.concurso);
-            anchor = res2.concurso;
+            match self.chamar(i, |p| p.fetch(concurso)) {
+                Ok(resultado) => return Ok(resultado),
+                Err(e) => {
+                    eprintln!("Provedor '{}' falhou: {}", provider.name(), e);
+                    falhas.push((provider.name(), e));
+                }
+            }
         }
+
+        Err(erro_da_cadeia(Some(concurso), &falhas))
     }
 
-    Ok(anchor)
-}
+    /// Consulta apenas as fontes oficiais, para conferir resultados
+    /// provisórios. `Ok(None)` quando nenhuma delas publicou o concurso ainda
+    /// (ou não há fonte oficial habilitada); `Err` quando todas falharam.
+    pub fn buscar_oficial(&self, concurso: i32) -> Result<Option<Resultado>, MegaSenaError> {
+        let mut falhas = Vec::new();
+        for i in (0..self.providers.len()).filter(|&i| self.providers[i].official()) {
+            match self.chamar(i, |p| p.fetch(concurso)) {
+                Ok(resultado) => return Ok(Some(resultado)),
+                Err(ErroBusca::NaoEncontrado) => {}
+                Err(e) => falhas.push((self.providers[i].name(), e)),
+            }
+        }
 
-pub fn verificar_resultado(concurso: i32) -> Result<Resultado, String> {
-    // 1. Tentar API Oficial da Caixa
-    println!("Tentando API Oficial para concurso {}", concurso);
-    
-    match fetch_caixa_api(concurso) {
-        Ok(resultado) => return Ok(resultado),
-        Err(e) => {
-            eprintln!("API Caixa falhou: {}", e);
+        if falhas.is_empty() {
+            Ok(None)
+        } else {
+            Err(erro_da_cadeia(Some(concurso), &falhas))
         }
     }
 
-    // 2. Tentar Fallback (APIs de terceiros / Open Source)
-    match fetch_external_fallback(concurso) {
-        Ok(resultado) => {
-            println!("Sucesso via Fallback para concurso {}", concurso);
-            return Ok(resultado);
-        },
-        Err(e) => {
-            eprintln!("Fallback falhou: {}", e);
+    /// Busca o concurso e, com a verificação cruzada ativada, consulta também
+    /// os demais provedores e compara dezenas e prêmio da Sena. O resultado
+    /// devolvido é sempre o do provedor de maior prioridade que respondeu.
+    pub fn buscar_verificado(&self, concurso: i32) -> Result<ResultadoBuscado, MegaSenaError> {
+        if !self.cross_validate {
+            return Ok(ResultadoBuscado {
+                resultado: self.verificar_resultado(concurso)?,
+                verificacao: None,
+            });
+        }
+
+        let mut respostas: Vec<(&str, Resultado)> = Vec::new();
+        let mut falhas = Vec::new();
+        for (i, provider) in self.providers.iter().enumerate() {
+            match self.chamar(i, |p| p.fetch(concurso)) {
+                Ok(resultado) => respostas.push((provider.name(), resultado)),
+                Err(e) => {
+                    eprintln!("Provedor '{}' falhou: {}", provider.name(), e);
+                    falhas.push((provider.name(), e));
+                }
+            }
+        }
+
+        let verificacao = comparar_respostas(concurso, &respostas);
+        let resultado = respostas
+            .into_iter()
+            .next()
+            .map(|(_, resultado)| resultado)
+            .ok_or_else(|| erro_da_cadeia(Some(concurso), &falhas))?;
+
+        Ok(ResultadoBuscado {
+            resultado,
+            verificacao: Some(verificacao),
+        })
+    }
+
+    /// Dispara a busca de vários concursos em paralelo, com no máximo
+    /// `max_concurrent` requisições em andamento. Cada tarefa pode ser
+    /// aguardada assim que terminar, na ordem de `concursos`. As chamadas
+    /// bloqueantes rodam em `spawn_blocking`, fora das threads do runtime.
+    pub fn iniciar_buscas(&self, concursos: &[i32]) -> Vec<(i32, BuscaEmAndamento)> {
+        let semaforo = Arc::new(Semaphore::new(self.max_concurrent));
+
+        concursos
+            .iter()
+            .map(|&concurso| {
+                let chain = self.clone();
+                let semaforo = semaforo.clone();
+                let tarefa = tokio::spawn(async move {
+                    let _permissao = semaforo
+                        .acquire_owned()
+                        .await
+                        .map_err(|e| MegaSenaError::Rede(e.to_string()))?;
+                    tokio::task::spawn_blocking(move || chain.buscar_verificado(concurso))
+                        .await
+                        .map_err(|e| MegaSenaError::Rede(e.to_string()))?
+                });
+                (concurso, BuscaEmAndamento(tarefa))
+            })
+            .collect()
+    }
+
+    /// Busca vários concursos em paralelo (ver `iniciar_buscas`). Os
+    /// resultados voltam na ordem de `concursos`.
+    pub async fn buscar_resultados(
+        &self,
+        concursos: &[i32],
+    ) -> Vec<(i32, Result<ResultadoBuscado, MegaSenaError>)> {
+        let mut resultados = Vec::with_capacity(concursos.len());
+        for (concurso, busca) in self.iniciar_buscas(concursos) {
+            resultados.push((concurso, busca.concluir().await));
+        }
+        resultados
+    }
+}
+
+/// Compara cada resposta com a do primeiro provedor da lista
+fn comparar_respostas(concurso: i32, respostas: &[(&str, Resultado)]) -> VerificacaoResultado {
+    let mut divergencias = Vec::new();
+
+    if let Some(((referencia, base), outras)) = respostas.split_first() {
+        for (nome, outro) in outras {
+            if outro.numeros_sorteados != base.numeros_sorteados {
+                divergencias.push(format!(
+                    "dezenas: {} {:?}, {} {:?}",
+                    referencia, base.numeros_sorteados, nome, outro.numeros_sorteados
+                ));
+            }
+            // Um provedor sem o rateio publicado ainda não contradiz o outro
+            if let (Some(a), Some(b)) = (base.ganhadores, outro.ganhadores) {
+                if a != b {
+                    divergencias.push(format!(
+                        "ganhadores da Sena: {} {}, {} {}",
+                        referencia, a, nome, b
+                    ));
+                }
+            }
+            if let (Some(a), Some(b)) = (base.valor_premio, outro.valor_premio) {
+                if (a - b).abs() >= 0.01 {
+                    divergencias.push(format!(
+                        "prêmio da Sena: {} {:.2}, {} {:.2}",
+                        referencia, a, nome, b
+                    ));
+                }
+            }
         }
     }
 
-    Err(format!(
-        "Não foi possível obter resultado do concurso {}. Isso pode ser devido a um atraso nos sistemas oficiais ou instabilidade na conexão. Tente novamente em alguns instantes.",
-        concurso
-    ))
+    let status = if !divergencias.is_empty() {
+        StatusVerificacao::Conflito
+    } else if respostas.len() < 2 {
+        StatusVerificacao::FonteUnica
+    } else {
+        StatusVerificacao::Concordante
+    };
+
+    VerificacaoResultado {
+        concurso,
+        status,
+        provedores: respostas.iter().map(|(nome, _)| nome.to_string()).collect(),
+        divergencias,
+        verificado_em: None,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{EstadoCircuito, StatusVerificacao};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    const PAYLOAD_2954: &str = r#"{
+        "numero": 2954,
+        "dataApuracao": "31/12/2025",
+        "listaDezenas": ["01", "09", "37", "39", "42", "44"],
+        "acumulado": false,
+        "listaRateioPremio": [
+            { "descricaoFaixa": "6 acertos", "faixa": 1, "numeroDeGanhadores": 6, "valorPremio": 181892881.09 },
+            { "descricaoFaixa": "5 acertos", "faixa": 2, "numeroDeGanhadores": 3142, "valorPremio": 21218.06 },
+            { "descricaoFaixa": "4 acertos", "faixa": 3, "numeroDeGanhadores": 235128, "valorPremio": 419.51 }
+        ],
+        "valorEstimadoProximoConcurso": 3500000.0,
+        "valorAcumuladoProximoConcurso": 0.0
+    }"#;
+
+    struct FakeProvider {
+        name: &'static str,
+        priority: i32,
+        resultado: Option<i32>,
+        oficial: bool,
+        chamadas: Arc<AtomicUsize>,
+    }
+
+    fn resultado_exemplo(concurso: i32) -> Resultado {
+        let data: CaixaApiResponse = serde_json::from_str(PAYLOAD_2954).unwrap();
+        let mut res = converter_resposta(data).unwrap();
+        res.concurso = concurso;
+        res
+    }
+
+    /// Provedor que devolve erro transitório nas primeiras `falhas` chamadas
+    struct FlakyProvider {
+        falhas: usize,
+        chamadas: Arc<AtomicUsize>,
+    }
+
+    impl ResultProvider for FlakyProvider {
+        fn name(&self) -> &str {
+            "instavel"
+        }
+
+        fn priority(&self) -> i32 {
+            0
+        }
+
+        fn fetch(&self, concurso: i32) -> Result<Resultado, ErroBusca> {
+            if self.chamadas.fetch_add(1, Ordering::SeqCst) < self.falhas {
+                return Err(ErroBusca::Transitorio("503".to_string()));
+            }
+            Ok(resultado_exemplo(concurso))
+        }
+
+        fn latest(&self) -> Result<Resultado, ErroBusca> {
+            self.fetch(3000)
+        }
+    }
+
+    /// Provedor que publica dezenas diferentes das oficiais
+    struct DivergentProvider;
+
+    impl ResultProvider for DivergentProvider {
+        fn name(&self) -> &str {
+            "divergente"
+        }
+
+        fn priority(&self) -> i32 {
+            10
+        }
+
+        fn fetch(&self, concurso: i32) -> Result<Resultado, ErroBusca> {
+            let mut res = resultado_exemplo(concurso);
+            res.numeros_sorteados = vec![2, 9, 37, 39, 42, 44];
+            Ok(res)
+        }
+
+        fn latest(&self) -> Result<Resultado, ErroBusca> {
+            self.fetch(3000)
+        }
+    }
+
+    fn retry_rapido(max_attempts: u32) -> RetryConfig {
+        RetryConfig {
+            max_attempts,
+            base_delay_ms: 1,
+            max_delay_ms: 2,
+        }
+    }
+
+    /// Provedor lento que registra o pico de chamadas simultâneas
+    struct SlowProvider {
+        em_andamento: AtomicUsize,
+        pico: Arc<AtomicUsize>,
+    }
+
+    impl ResultProvider for SlowProvider {
+        fn name(&self) -> &str {
+            "lento"
+        }
+
+        fn priority(&self) -> i32 {
+            0
+        }
+
+        fn fetch(&self, concurso: i32) -> Result<Resultado, ErroBusca> {
+            let atual = self.em_andamento.fetch_add(1, Ordering::SeqCst) + 1;
+            self.pico.fetch_max(atual, Ordering::SeqCst);
+            // Concursos maiores terminam antes, para embaralhar a ordem de conclusão
+            std::thread::sleep(Duration::from_millis(10 + (3000 - concurso) as u64 * 5));
+            self.em_andamento.fetch_sub(1, Ordering::SeqCst);
+
+            if concurso == 2995 {
+                return Err(ErroBusca::NaoEncontrado);
+            }
+            Ok(resultado_exemplo(concurso))
+        }
+
+        fn latest(&self) -> Result<Resultado, ErroBusca> {
+            self.fetch(3000)
+        }
+    }
+
+    impl ResultProvider for FakeProvider {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+
+        fn fetch(&self, concurso: i32) -> Result<Resultado, ErroBusca> {
+            self.chamadas.fetch_add(1, Ordering::SeqCst);
+            match self.resultado {
+                Some(ultimo) if concurso <= ultimo => Ok(resultado_exemplo(concurso)),
+                _ => Err(ErroBusca::NaoEncontrado),
+            }
+        }
+
+        fn latest(&self) -> Result<Resultado, ErroBusca> {
+            match self.resultado {
+                Some(ultimo) => self.fetch(ultimo),
+                None => Err(ErroBusca::NaoEncontrado),
+            }
+        }
+
+        fn official(&self) -> bool {
+            self.oficial
+        }
+    }
+
+    fn fake(
+        name: &'static str,
+        priority: i32,
+        resultado: Option<i32>,
+    ) -> (Box<dyn ResultProvider>, Arc<AtomicUsize>) {
+        let chamadas = Arc::new(AtomicUsize::new(0));
+        let provider = FakeProvider {
+            name,
+            priority,
+            resultado,
+            oficial: false,
+            chamadas: chamadas.clone(),
+        };
+        (Box::new(provider), chamadas)
+    }
+
+    #[test]
+    fn test_converter_resposta_caixa() {
+        let data: CaixaApiResponse = serde_json::from_str(PAYLOAD_2954).unwrap();
+        let res = converter_resposta(data).unwrap();
+
+        assert_eq!(res.concurso, 2954);
+        assert_eq!(res.numeros_sorteados, vec![1, 9, 37, 39, 42, 44]);
+        assert_eq!(res.ganhadores, Some(6));
+        assert_eq!(res.valor_premio, Some(181892881.09));
+
+        assert_eq!(res.faixas.len(), 3);
+        assert_eq!(
+            res.faixas[1],
+            FaixaPremio {
+                faixa: 2,
+                acertos: 5,
+                ganhadores: 3142,
+                valor_premio: 21218.06,
+            }
+        );
+        assert_eq!(res.faixas[2].acertos, 4);
+    }
+
+    #[test]
+    fn test_interpretar_payload() {
+        let res = interpretar_payload(PAYLOAD_2954).unwrap();
+        assert_eq!(res.concurso, 2954);
+        assert!(res.payload.is_none());
+        assert!(interpretar_payload("<html>manutenção</html>").is_err());
+    }
+
+    #[test]
+    fn test_proximo_concurso() {
+        let json = PAYLOAD_2954.replace(
+            r#""valorEstimadoProximoConcurso""#,
+            r#""numeroConcursoProximo": 2955, "dataProximoConcurso": "03/01/2026", "valorEstimadoProximoConcurso""#,
+        );
+        let mut res = interpretar_payload(&json).unwrap();
+        res.payload = Some(json);
+
+        let proximo = proximo_concurso(&res);
+        assert_eq!(proximo.concurso, 2955);
+        assert_eq!(proximo.data_sorteio.as_deref(), Some("03/01/2026"));
+        assert_eq!(proximo.valor_estimado, Some(3500000.0));
+
+        // Sem a resposta bruta, só o número é deduzido
+        res.payload = None;
+        let proximo = proximo_concurso(&res);
+        assert_eq!(proximo.concurso, 2955);
+        assert!(proximo.valor_estimado.is_none());
+    }
+
+    #[test]
+    fn test_converter_faixa_sem_numero_da_faixa() {
+        // Algumas fontes omitem o campo "faixa"; a descrição é usada no lugar
+        let json = r#"{ "descricaoFaixa": "5 acertos", "numeroDeGanhadores": 10, "valorPremio": 50000.0 }"#;
+        let rateio: Rateio = serde_json::from_str(json).unwrap();
+        let faixa = converter_faixa(&rateio).unwrap();
+        assert_eq!(faixa.faixa, 2);
+        assert_eq!(faixa.acertos, 5);
+    }
+
+    #[test]
+    fn test_converter_resposta_dezenas_incompletas() {
+        let json = PAYLOAD_2954.replace(r#""01", "09", "#, "");
+        let data: CaixaApiResponse = serde_json::from_str(&json).unwrap();
+        assert!(converter_resposta(data).is_err());
+    }
+
+    #[test]
+    fn test_chain_respeita_prioridade() {
+        let (secundario, chamadas_secundario) = fake("secundario", 10, Some(3000));
+        let (principal, chamadas_principal) = fake("principal", 0, None);
+        let chain = ProviderChain::new(vec![secundario, principal]);
+
+        assert_eq!(chain.provider_names(), vec!["principal", "secundario"]);
+
+        let res = chain.verificar_resultado(2950).unwrap();
+        assert_eq!(res.concurso, 2950);
+        assert_eq!(chamadas_principal.load(Ordering::SeqCst), 1);
+        assert_eq!(chamadas_secundario.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_fallback_marca_resultado_provisorio() {
+        let oficial = FakeProvider {
+            name: "oficial",
+            priority: 0,
+            resultado: Some(2949),
+            oficial: true,
+            chamadas: Arc::new(AtomicUsize::new(0)),
+        };
+        let (reserva, _) = fake("reserva", 10, Some(3000));
+        let chain = ProviderChain::new(vec![Box::new(oficial), reserva]);
+
+        let res = chain.verificar_resultado(2950).unwrap();
+        assert_eq!(res.fonte.as_deref(), Some("reserva"));
+        assert!(res.provisional);
+        assert!(res.fetched_at.is_some());
+
+        let res = chain.verificar_resultado(2949).unwrap();
+        assert_eq!(res.fonte.as_deref(), Some("oficial"));
+        assert!(!res.provisional);
+
+        // Conferência: só a fonte oficial é consultada
+        assert!(chain.buscar_oficial(2950).unwrap().is_none());
+        assert!(!chain.buscar_oficial(2949).unwrap().unwrap().provisional);
+    }
+
+    #[test]
+    fn test_chain_vazia_retorna_erro() {
+        let chain = ProviderChain::new(Vec::new());
+        assert!(chain.verificar_resultado(2950).is_err());
+    }
+
+    #[test]
+    fn test_from_config_ignora_desabilitados() {
+        let mut config = ApiConfig::default();
+        config.providers[0].enabled = false;
+        let chain = ProviderChain::from_config(&config);
+        assert_eq!(chain.provider_names(), vec!["guidi"]);
+    }
+
+    #[test]
+    fn test_ultimo_concurso_usa_fallback() {
+        let (principal, _) = fake("principal", 0, None);
+        let (secundario, _) = fake("secundario", 10, Some(2955));
+        let chain = ProviderChain::new(vec![principal, secundario]);
+        assert_eq!(chain.obter_ultimo_concurso_numero(None).unwrap(), 2955);
+    }
+
+    /// Meio-dia (UTC) `dias` depois da data de referência
+    fn dias_depois(referencia: calendario::Referencia, dias: i64) -> chrono::DateTime<chrono::Utc> {
+        (referencia.data + chrono::Duration::days(dias))
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    #[test]
+    fn test_ultimo_concurso_offline_usa_calendario() {
+        let chain = ProviderChain::new(Vec::new());
+        let cache = resultado_exemplo(2990);
+        let referencia =
+            calendario::melhor_referencia(calendario::Referencia::nova(2990, &cache.data_sorteio));
+        let agora = dias_depois(referencia, 10);
+
+        let ultimo = chain
+            .obter_ultimo_concurso_numero_em(Some(&cache), agora)
+            .unwrap();
+        assert!(ultimo > referencia.concurso);
+        assert_eq!(
+            ultimo,
+            calendario::estimar_concurso_atual(referencia, agora)
+        );
+    }
+
+    #[test]
+    fn test_ultimo_concurso_sondagem_para_no_ausente() {
+        let (principal, chamadas) = fake("principal", 0, Some(2951));
+        let chain = ProviderChain::new(vec![principal]);
+        let publicado = resultado_exemplo(2951);
+        let referencia = calendario::melhor_referencia(calendario::Referencia::nova(
+            2951,
+            &publicado.data_sorteio,
+        ));
+        // Dez dias depois da referência o calendário prevê novos concursos;
+        // a sondagem para no primeiro ausente
+        let agora = dias_depois(referencia, 10);
+        assert!(calendario::estimar_concurso_atual(referencia, agora) > referencia.concurso);
+
+        assert_eq!(
+            chain.obter_ultimo_concurso_numero_em(None, agora).unwrap(),
+            referencia.concurso.max(2951)
+        );
+        // latest + primeiro concurso ausente
+        assert_eq!(chamadas.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_retry_recupera_falha_transitoria() {
+        let chamadas = Arc::new(AtomicUsize::new(0));
+        let instavel = FlakyProvider {
+            falhas: 2,
+            chamadas: chamadas.clone(),
+        };
+        let chain = ProviderChain::new(vec![Box::new(instavel)]).with_retry(retry_rapido(3));
+
+        assert_eq!(chain.verificar_resultado(2950).unwrap().concurso, 2950);
+        assert_eq!(chamadas.load(Ordering::SeqCst), 3);
+        assert_eq!(chain.diagnostico()[0].estado, EstadoCircuito::Fechado);
+    }
+
+    #[test]
+    fn test_nao_encontrado_nao_repete_nem_abre_circuito() {
+        let (principal, chamadas) = fake("principal", 0, Some(2950));
+        let chain = ProviderChain::new(vec![principal])
+            .with_retry(retry_rapido(5))
+            .with_circuit_breaker(CircuitBreakerConfig {
+                failure_threshold: 1,
+                cooldown_secs: 60,
+            });
+
+        assert!(chain.verificar_resultado(2951).is_err());
+        assert!(chain.verificar_resultado(2951).is_err());
+        assert_eq!(chamadas.load(Ordering::SeqCst), 2);
+        assert_eq!(chain.diagnostico()[0].estado, EstadoCircuito::Fechado);
+    }
+
+    #[test]
+    fn test_erro_distingue_nao_sorteado_de_rede() {
+        let (principal, _) = fake("principal", 0, Some(2950));
+        let chain = ProviderChain::new(vec![principal]);
+        assert_eq!(
+            chain.verificar_resultado(2951).unwrap_err(),
+            MegaSenaError::NaoSorteado { concurso: 2951 }
+        );
+
+        let instavel = FlakyProvider {
+            falhas: 10,
+            chamadas: Arc::new(AtomicUsize::new(0)),
+        };
+        let chain = ProviderChain::new(vec![Box::new(instavel)]).with_retry(retry_rapido(1));
+        assert_eq!(
+            chain.verificar_resultado(2951).unwrap_err().code(),
+            "network"
+        );
+    }
+
+    #[test]
+    fn test_circuito_aberto_pula_provedor() {
+        let chamadas = Arc::new(AtomicUsize::new(0));
+        let instavel = FlakyProvider {
+            falhas: usize::MAX,
+            chamadas: chamadas.clone(),
+        };
+        let (reserva, chamadas_reserva) = fake("reserva", 10, Some(3000));
+        let chain = ProviderChain::new(vec![Box::new(instavel), reserva])
+            .with_retry(retry_rapido(2))
+            .with_circuit_breaker(CircuitBreakerConfig {
+                failure_threshold: 2,
+                cooldown_secs: 60,
+            });
+
+        for _ in 0..3 {
+            assert!(chain.verificar_resultado(2950).is_ok());
+        }
+        // Duas chamadas lógicas com 2 tentativas cada; a terceira é pulada
+        assert_eq!(chamadas.load(Ordering::SeqCst), 4);
+        assert_eq!(chamadas_reserva.load(Ordering::SeqCst), 3);
+
+        let diagnostico = chain.diagnostico();
+        assert_eq!(diagnostico[0].nome, "instavel");
+        assert_eq!(diagnostico[0].estado, EstadoCircuito::Aberto);
+        assert_eq!(diagnostico[0].ultimo_erro.as_deref(), Some("503"));
+        assert!(diagnostico[0].reabre_em_segundos.is_some());
+        assert_eq!(diagnostico[1].estado, EstadoCircuito::Fechado);
+    }
+
+    #[test]
+    fn test_verificacao_cruzada_concordante() {
+        let (principal, _) = fake("principal", 0, Some(3000));
+        let (secundario, chamadas_secundario) = fake("secundario", 10, Some(3000));
+        let chain = ProviderChain::new(vec![principal, secundario]).with_cross_validation(true);
+
+        let buscado = chain.buscar_verificado(2950).unwrap();
+        let verificacao = buscado.verificacao.unwrap();
+        assert_eq!(verificacao.status, StatusVerificacao::Concordante);
+        assert_eq!(verificacao.provedores, vec!["principal", "secundario"]);
+        assert_eq!(chamadas_secundario.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_verificacao_cruzada_conflito() {
+        let (principal, _) = fake("principal", 0, Some(3000));
+        let chain = ProviderChain::new(vec![principal, Box::new(DivergentProvider)])
+            .with_cross_validation(true);
+
+        let verificacao = chain.buscar_verificado(2950).unwrap().verificacao.unwrap();
+        assert_eq!(verificacao.status, StatusVerificacao::Conflito);
+        assert_eq!(verificacao.divergencias.len(), 1);
+        assert!(verificacao.divergencias[0].starts_with("dezenas"));
+    }
+
+    #[test]
+    fn test_verificacao_cruzada_fonte_unica_e_desativada() {
+        let (principal, _) = fake("principal", 0, Some(3000));
+        let (secundario, chamadas_secundario) = fake("secundario", 10, None);
+        let chain = ProviderChain::new(vec![principal, secundario]);
+
+        // Desativada: só o primeiro provedor é consultado
+        assert!(chain.buscar_verificado(2950).unwrap().verificacao.is_none());
+        assert_eq!(chamadas_secundario.load(Ordering::SeqCst), 0);
+
+        let chain = chain.with_cross_validation(true);
+        let verificacao = chain.buscar_verificado(2950).unwrap().verificacao.unwrap();
+        assert_eq!(verificacao.status, StatusVerificacao::FonteUnica);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_buscar_resultados_ordenado_e_limitado() {
+        let pico = Arc::new(AtomicUsize::new(0));
+        let lento = SlowProvider {
+            em_andamento: AtomicUsize::new(0),
+            pico: pico.clone(),
+        };
+        let chain = ProviderChain::new(vec![Box::new(lento)]).with_max_concurrent(3);
+
+        let concursos: Vec<i32> = (2990..=3000).rev().collect();
+        let resultados = chain.buscar_resultados(&concursos).await;
+
+        let ordem: Vec<i32> = resultados.iter().map(|(c, _)| *c).collect();
+        assert_eq!(ordem, concursos);
+        for (concurso, resultado) in &resultados {
+            match resultado {
+                Ok(r) => assert_eq!(r.resultado.concurso, *concurso),
+                Err(_) => assert_eq!(*concurso, 2995),
+            }
+        }
+        assert!(pico.load(Ordering::SeqCst) <= 3);
+        assert!(pico.load(Ordering::SeqCst) > 1);
+    }
 
     #[test]
     #[ignore] // Ignora por padrão (requer internet)
     fn test_fetch_caixa_api() {
         // Testar com um concurso recente conhecido
-        let resultado = fetch_caixa_api(2650);
+        let resultado = CaixaProvider::new(0, 10).unwrap().fetch(2650);
         assert!(resultado.is_ok());
-        
+
         let res = resultado.unwrap();
         assert_eq!(res.concurso, 2650);
         assert_eq!(res.numeros_sorteados.len(), 6);
@@ -278,7 +1347,7 @@ mod tests {
     #[ignore]
     fn test_fetch_fallback_api() {
         // Testar fallback com o concurso 2954 que sabemos estar disponível
-        let resultado = fetch_external_fallback(2954);
+        let resultado = GuidiProvider::new(0, 10).unwrap().fetch(2954);
         assert!(resultado.is_ok());
         let res = resultado.unwrap();
         assert_eq!(res.concurso, 2954);
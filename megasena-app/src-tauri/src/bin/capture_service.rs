@@ -1,4 +1,6 @@
-use megasena_app_lib::{api, database::Database};
+use megasena_app_lib::{
+    api::ProviderChain, config::AppConfig, database::Database, db_actor::DbActor, importacao,
+};
 use std::path::PathBuf;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -12,15 +14,59 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // Garantir que a pasta existe (embora o app já deva ter criado)
     std::fs::create_dir_all(&db_path)?;
+    let config = AppConfig::load(&db_path.join("config.json"));
+    let providers = ProviderChain::from_config(&config.api);
     db_path.push("megasena.db");
 
     println!("Conectando ao banco: {:?}", db_path);
-    let db = Database::new(db_path.clone())?;
+    let mut db = Database::new(db_path.clone())?;
     db.init()?;
 
+    // Modo importação: `capture_service --importar <arquivo>` carrega o
+    // histórico completo a partir da planilha da Caixa (XLSX/HTML) ou CSV
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(pos) = args.iter().position(|a| a == "--importar") {
+        let arquivo = args.get(pos + 1).ok_or("Uso: capture_service --importar <arquivo>")?;
+        println!("\nImportando histórico de {}...", arquivo);
+
+        let db = DbActor::iniciar(db);
+        let resumo = tokio::runtime::Runtime::new()?.block_on(importacao::importar_arquivo(
+            &db,
+            PathBuf::from(arquivo),
+            &|feitos, total| println!("  {}/{} concursos processados", feitos, total),
+        ))?;
+
+        println!("--------------------------------------------------");
+        println!("Linhas na planilha:    {}", resumo.total);
+        println!("Novos:                 {}", resumo.novos);
+        println!("Atualizados:           {}", resumo.atualizados);
+        println!("Inalterados:           {}", resumo.inalterados);
+        println!("Linhas ignoradas:      {}", resumo.linhas_ignoradas);
+        println!("--------------------------------------------------");
+        return Ok(());
+    }
+
+    // Modo reprocessamento: `capture_service --reprocessar` reinterpreta as
+    // respostas guardadas em resultados_raw, sem acessar a rede
+    if args.iter().any(|a| a == "--reprocessar") {
+        println!("\nReprocessando respostas armazenadas...");
+        let db = DbActor::iniciar(db);
+        let resumo = tokio::runtime::Runtime::new()?
+            .block_on(importacao::reprocessar_respostas(&db, &|_, _| {}))?;
+
+        println!("--------------------------------------------------");
+        println!("Respostas armazenadas: {}", resumo.total);
+        println!("Atualizados:           {}", resumo.atualizados);
+        println!("Inalterados:           {}", resumo.inalterados);
+        println!("Ilegíveis:             {}", resumo.linhas_ignoradas);
+        println!("--------------------------------------------------");
+        return Ok(());
+    }
+
     // 2. Identificar Último Concurso (Âncora com Exploração)
     println!("\n[1/3] Identificando Horizonte de Concursos...");
-    let ultimo_numero = api::obter_ultimo_concurso_numero().map_err(|e| e.to_string())?;
+    let cache = db.obter_ultimo_resultado()?;
+    let ultimo_numero = providers.obter_ultimo_concurso_numero(cache.as_ref()).map_err(|e| e.to_string())?;
     println!("Último concurso detectado: #{}", ultimo_numero);
 
     // 3. Executar Regra dos 36 (Puxar os últimos 36)
@@ -34,10 +80,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut anchor_success = false;
 
     for concurso in range {
-        match api::verificar_resultado(concurso) {
-            Ok(resultado) => {
-                db.salvar_resultado(&resultado)?;
-                db.processar_acertos_concurso(concurso, &resultado.numeros_sorteados)?;
+        match providers.buscar_verificado(concurso) {
+            Ok(buscado) => {
+                let resultado = buscado.resultado;
+                if !db.salvar_resultado_verificado(&resultado, buscado.verificacao.as_ref())? {
+                    eprintln!("  ⚠️ Concurso #{}: provedores divergem, resultado não salvo", concurso);
+                    continue;
+                }
+                db.processar_acertos_concurso(concurso)?;
                 println!("  ✅ Concurso #{}: Sorteado em {} - [{}]", 
                     resultado.concurso, 
                     resultado.data_sorteio,
@@ -0,0 +1,71 @@
+use megasena_app_lib::fake_loteria::{
+    carregar_fixtures, FakeLoteriaServer, InjecaoFalhas, ModoFalha,
+};
+use std::path::PathBuf;
+use std::time::Duration;
+
+const USO: &str = "Uso: fake_loteria_server [--porta 8787] [--fixtures <dir>] [--falha 500|timeout|json] [--a-cada N] [--limite N] [--atraso-timeout-secs N]";
+
+fn valor<'a>(args: &'a [String], nome: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == nome)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", USO);
+        return Ok(());
+    }
+
+    let porta: u16 = valor(&args, "--porta").unwrap_or("8787").parse()?;
+    let diretorio = valor(&args, "--fixtures")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/megasena"));
+    let fixtures = carregar_fixtures(&diretorio)?;
+
+    let injecao = match valor(&args, "--falha") {
+        Some(nome) => {
+            let modo = ModoFalha::from_nome(nome).ok_or(USO)?;
+            let mut injecao = InjecaoFalhas::new(modo);
+            if let Some(n) = valor(&args, "--a-cada") {
+                injecao.a_cada = n.parse()?;
+            }
+            if let Some(n) = valor(&args, "--limite") {
+                injecao.limite = Some(n.parse()?);
+            }
+            if let Some(n) = valor(&args, "--atraso-timeout-secs") {
+                injecao.atraso_timeout = Duration::from_secs(n.parse()?);
+            }
+            Some(injecao)
+        }
+        None => None,
+    };
+
+    let servidor = FakeLoteriaServer::iniciar(
+        &format!("127.0.0.1:{}", porta),
+        fixtures.clone(),
+        injecao.clone(),
+    )?;
+
+    println!("=== Fake Loteria Server ===");
+    println!("{} concursos carregados de {:?}", fixtures.len(), diretorio);
+    if let Some(injecao) = &injecao {
+        println!("Injeção de falhas: {:?}", injecao);
+    }
+    println!("\nUse no config.json:");
+    println!(
+        "  {{ \"name\": \"caixa\", \"baseUrl\": \"{}\" }}",
+        servidor.url_base("caixa")
+    );
+    println!(
+        "  {{ \"name\": \"guidi\", \"baseUrl\": \"{}\" }}",
+        servidor.url_base("guidi")
+    );
+
+    loop {
+        std::thread::park();
+    }
+}
@@ -0,0 +1,190 @@
+/*
+ * MegaSena Monitor - Minimalist desktop application for managing bets.
+ * Copyright (C) 2025 Zander Cattapreta
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Draw calendar: which days have a Mega-Sena draw and which contest should exist now
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::America::Sao_Paulo;
+use chrono_tz::Tz;
+
+/// Dias da semana com sorteio regular
+pub const DIAS_SORTEIO: [Weekday; 3] = [Weekday::Tue, Weekday::Thu, Weekday::Sat];
+
+/// Horário dos sorteios, no horário de Brasília
+pub const HORA_SORTEIO: u32 = 20;
+
+/// Concursos especiais conhecidos (número, data). Além de marcar sorteios
+/// fora da grade, servem de referência quando ainda não há nada em cache.
+pub const SORTEIOS_ESPECIAIS: &[(i32, (i32, u32, u32))] = &[
+    // Mega da Virada 2025
+    (2954, (2025, 12, 31)),
+];
+
+/// Data de um concurso conhecido, usada como ponto de partida da estimativa
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Referencia {
+    pub concurso: i32,
+    pub data: NaiveDate,
+}
+
+impl Referencia {
+    /// A partir do número e da data no formato da API ("dd/mm/aaaa")
+    pub fn nova(concurso: i32, data_sorteio: &str) -> Option<Self> {
+        let data = NaiveDate::parse_from_str(data_sorteio.trim(), "%d/%m/%Y").ok()?;
+        Some(Referencia { concurso, data })
+    }
+}
+
+fn data(ano: i32, mes: u32, dia: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(ano, mes, dia).expect("data válida na tabela de sorteios")
+}
+
+/// Mega da Virada: todo 31 de dezembro, caia ou não em dia de sorteio regular
+fn eh_virada(dia: NaiveDate) -> bool {
+    dia.month() == 12 && dia.day() == 31
+}
+
+/// Natal e Ano-Novo não têm sorteio, mesmo caindo em dia da grade
+fn eh_feriado_sem_sorteio(dia: NaiveDate) -> bool {
+    matches!((dia.month(), dia.day()), (12, 25) | (1, 1))
+}
+
+/// Indica se há sorteio da Mega-Sena na data
+pub fn eh_dia_de_sorteio(dia: NaiveDate) -> bool {
+    (DIAS_SORTEIO.contains(&dia.weekday()) && !eh_feriado_sem_sorteio(dia))
+        || eh_virada(dia)
+        || SORTEIOS_ESPECIAIS
+            .iter()
+            .any(|&(_, (a, m, d))| data(a, m, d) == dia)
+}
+
+/// Momento do sorteio em uma data, no fuso de São Paulo
+pub fn horario_sorteio(dia: NaiveDate) -> DateTime<Tz> {
+    let hora = NaiveTime::from_hms_opt(HORA_SORTEIO, 0, 0).expect("hora válida");
+    Sao_Paulo
+        .from_local_datetime(&dia.and_time(hora))
+        .earliest()
+        .expect("horário de sorteio existe no fuso de São Paulo")
+}
+
+/// Referência mais recente entre as informadas (ex.: último concurso em
+/// cache ou publicado pela API) e a tabela de sorteios especiais
+pub fn melhor_referencia(candidatas: impl IntoIterator<Item = Referencia>) -> Referencia {
+    SORTEIOS_ESPECIAIS
+        .iter()
+        .map(|&(concurso, (a, m, d))| Referencia {
+            concurso,
+            data: data(a, m, d),
+        })
+        .chain(candidatas)
+        .max_by_key(|r| (r.concurso, r.data))
+        .expect("tabela de sorteios especiais não vazia")
+}
+
+/// Número de sorteios realizados depois do dia `desde` até `agora`
+fn sorteios_desde(desde: NaiveDate, agora: DateTime<Utc>) -> i32 {
+    let agora = agora.with_timezone(&Sao_Paulo);
+    desde
+        .iter_days()
+        .skip(1)
+        .take_while(|dia| *dia <= agora.date_naive())
+        .filter(|dia| eh_dia_de_sorteio(*dia) && horario_sorteio(*dia) <= agora)
+        .count() as i32
+}
+
+/// Concurso que já deveria ter sido sorteado em `agora`, contando os
+/// sorteios da grade desde a referência
+pub fn estimar_concurso_atual(referencia: Referencia, agora: DateTime<Utc>) -> i32 {
+    referencia.concurso + sorteios_desde(referencia.data, agora)
+}
+
+/// Próximo sorteio estritamente depois de `agora`
+pub fn proximo_sorteio(agora: DateTime<Utc>) -> DateTime<Tz> {
+    let local = agora.with_timezone(&Sao_Paulo);
+    local
+        .date_naive()
+        .iter_days()
+        .filter(|dia| eh_dia_de_sorteio(*dia))
+        .map(horario_sorteio)
+        .find(|horario| *horario > local)
+        .expect("sempre há um próximo dia de sorteio")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    fn em_sao_paulo(ano: i32, mes: u32, dia: u32, hora: u32) -> DateTime<Utc> {
+        Sao_Paulo
+            .with_ymd_and_hms(ano, mes, dia, hora, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_dias_de_sorteio() {
+        assert!(eh_dia_de_sorteio(data(2026, 1, 3))); // sábado
+        assert!(!eh_dia_de_sorteio(data(2026, 1, 4))); // domingo
+        assert!(eh_dia_de_sorteio(data(2025, 12, 31))); // Virada numa quarta
+        assert!(eh_dia_de_sorteio(data(2026, 12, 31)));
+        assert!(!eh_dia_de_sorteio(data(2026, 1, 1))); // Ano-Novo numa quinta
+    }
+
+    #[test]
+    fn test_estimar_concurso_atual() {
+        let virada = Referencia::nova(2954, "31/12/2025").unwrap();
+
+        // Sem sorteio no Ano-Novo; o próximo é o de sábado, 03/01, às 20h
+        assert_eq!(
+            estimar_concurso_atual(virada, em_sao_paulo(2026, 1, 3, 19)),
+            2954
+        );
+        assert_eq!(
+            estimar_concurso_atual(virada, em_sao_paulo(2026, 1, 3, 21)),
+            2955
+        );
+        // Uma semana inteira: terça, quinta e sábado
+        assert_eq!(
+            estimar_concurso_atual(virada, em_sao_paulo(2026, 1, 11, 12)),
+            2958
+        );
+    }
+
+    #[test]
+    fn test_melhor_referencia_prefere_cache_recente() {
+        let cache = Referencia::nova(2960, "13/01/2026").unwrap();
+        assert_eq!(melhor_referencia(Some(cache)), cache);
+
+        let antigo = Referencia::nova(2700, "02/03/2024").unwrap();
+        assert_eq!(melhor_referencia(Some(antigo)).concurso, 2954);
+        assert_eq!(melhor_referencia(None).concurso, 2954);
+    }
+
+    #[test]
+    fn test_proximo_sorteio() {
+        // Sábado antes das 20h: o próprio sábado
+        let proximo = proximo_sorteio(em_sao_paulo(2026, 1, 3, 15));
+        assert_eq!(proximo.date_naive(), data(2026, 1, 3));
+        assert_eq!(proximo.hour(), HORA_SORTEIO);
+
+        // Depois do sorteio: a terça seguinte
+        let proximo = proximo_sorteio(em_sao_paulo(2026, 1, 3, 21));
+        assert_eq!(proximo.date_naive(), data(2026, 1, 6));
+    }
+}
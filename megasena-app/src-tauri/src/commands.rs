@@ -16,172 +16,515 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::database::Database;
-use crate::models::{Aposta, Resultado};
-use crate::api;
-use std::sync::Mutex;
-use tauri::State;
+use crate::agendador::Agendador;
+use crate::api::{self, ProviderChain};
+use crate::calendario;
+use crate::db_actor::DbActor;
+use crate::domain::{Concurso, Dezenas, QuantidadeConcursos};
+use crate::error::MegaSenaError;
+use crate::eventos::{ApostaAlterada, Evento};
+use crate::importacao;
+use crate::models::{
+    Aposta, DiagnosticoProvedor, FiltroApostas, PreferenciasNotificacao, PremioAposta,
+    ProximoConcurso, Resultado, ResumoImportacao, ResumoReconciliacao, ResumoSincronizacao,
+    VerificacaoResultado,
+};
+use crate::notificacoes;
+use crate::reconciliacao;
+use crate::sincronizacao;
+use std::collections::HashMap;
+use tauri::{AppHandle, Emitter, State};
 
 #[tauri::command]
-pub fn adicionar_aposta(
-    db: State<'_, Mutex<Database>>,
+pub async fn adicionar_aposta(
+    app: AppHandle,
+    db: State<'_, DbActor>,
     numeros: Vec<i32>,
-    concurso_inicial: i32,
+    concurso_inicial: Option<i32>,
     quantidade_concursos: i32,
-) -> Result<Aposta, String> {
+) -> Result<Aposta, MegaSenaError> {
+    // Sem concurso informado, a aposta vale a partir do próximo sorteio,
+    // estimado pelo cache e pelo calendário, sem esperar a rede
+    let concurso_inicial = match concurso_inicial {
+        Some(concurso) => concurso,
+        None => {
+            let cache = db.executar_async(|db| db.obter_ultimo_resultado()).await??;
+            proximo_pelo_calendario(cache, chrono::Utc::now()).concurso
+        }
+    };
     println!("Comando adicionar_aposta: concurso={}, qtd={}", concurso_inicial, quantidade_concursos);
-    let db = db.lock().map_err(|e| e.to_string())?;
-    
-    // Validações
-    if numeros.len() < 6 || numeros.len() > 20 {
-        return Err("Selecione entre 6 e 20 números".to_string());
-    }
-    
-    if concurso_inicial <= 0 {
-        return Err("Concurso inválido".to_string());
-    }
-    
-    if quantidade_concursos < 1 || quantidade_concursos > 12 {
-        return Err("Quantidade de concursos deve ser entre 1 e 12".to_string());
+    let numeros = Dezenas::novas(numeros)?;
+    let concurso_inicial = Concurso::novo(concurso_inicial)?;
+    let quantidade_concursos = QuantidadeConcursos::nova(quantidade_concursos)?;
+
+    let aposta = db
+        .executar_async(move |db| db.adicionar_aposta(&numeros, concurso_inicial, quantidade_concursos))
+        .await??;
+    aposta_alterada(&app, aposta.id);
+    Ok(aposta)
+}
+
+#[tauri::command]
+pub fn atualizar_aposta(
+    app: AppHandle,
+    db: State<'_, DbActor>,
+    id: i64,
+    numeros: Vec<i32>,
+    concurso_inicial: i32,
+    quantidade_concursos: i32,
+) -> Result<Aposta, MegaSenaError> {
+    println!("Comando atualizar_aposta: id={}, concurso={}, qtd={}", id, concurso_inicial, quantidade_concursos);
+    let numeros = Dezenas::novas(numeros)?;
+    let concurso_inicial = Concurso::novo(concurso_inicial)?;
+    let quantidade_concursos = QuantidadeConcursos::nova(quantidade_concursos)?;
+
+    let aposta = db.executar(move |db| db.atualizar_aposta(id, &numeros, concurso_inicial, quantidade_concursos))?
+        .map_err(|e| erro_aposta(id, e))?;
+    aposta_alterada(&app, id);
+    Ok(aposta)
+}
+
+#[tauri::command]
+pub fn listar_apostas(
+    db: State<'_, DbActor>,
+    filtro: Option<FiltroApostas>,
+) -> Result<Vec<Aposta>, MegaSenaError> {
+    println!("Comando listar_apostas recebido: filtro={:?}", filtro);
+    let filtro = filtro.unwrap_or_default();
+    db.executar(move |db| db.listar_apostas_filtro(filtro))?
+        .map_err(MegaSenaError::from)
+}
+
+/// Mensagem de erro comum às operações sobre uma aposta existente
+fn erro_aposta(id: i64, e: rusqlite::Error) -> MegaSenaError {
+    match e {
+        rusqlite::Error::QueryReturnedNoRows => {
+            MegaSenaError::NaoEncontrado(format!("Aposta {} não encontrada", id))
+        }
+        e => e.into(),
     }
+}
 
-    db.adicionar_aposta(numeros, concurso_inicial, quantidade_concursos)
-        .map_err(|e| e.to_string())
+/// Avisa as janelas de que a aposta foi criada, editada ou mudou de estado
+fn aposta_alterada(app: &AppHandle, aposta_id: i64) {
+    Evento::ApostaAlterada(ApostaAlterada { aposta_id }).emitir(app);
 }
 
 #[tauri::command]
-pub fn listar_apostas(db: State<'_, Mutex<Database>>) -> Result<Vec<Aposta>, String> {
-    println!("Comando listar_apostas recebido");
-    let db = db.lock().map_err(|e| e.to_string())?;
-    db.listar_apostas().map_err(|e| e.to_string())
-}#[tauri::command]
-pub fn excluir_aposta(db: State<'_, Mutex<Database>>, id: i64) -> Result<(), String> {
+pub fn excluir_aposta(app: AppHandle, db: State<'_, DbActor>, id: i64) -> Result<(), MegaSenaError> {
     println!(">>> Comando excluir_aposta SOLICITADO para ID: {}", id);
-    let db = db.lock().map_err(|e| e.to_string())?;
-    match db.excluir_aposta(id) {
+    match db.executar(move |db| db.mover_para_lixeira(id))? {
         Ok(_) => {
-            println!(">>> Aposta {} EXCLUÍDA com sucesso do banco.", id);
+            println!(">>> Aposta {} movida para a lixeira.", id);
+            aposta_alterada(&app, id);
             Ok(())
         },
         Err(e) => {
-            let err_msg = format!("ERRO NO BANCO ao excluir aposta {}: {}", id, e);
-            eprintln!("{}", err_msg);
-            Err(err_msg)
+            let erro = erro_aposta(id, e);
+            eprintln!("ERRO NO BANCO ao excluir aposta {}: {}", id, erro);
+            Err(erro)
         }
     }
 }
 
+#[tauri::command]
+pub fn arquivar_aposta(app: AppHandle, db: State<'_, DbActor>, id: i64) -> Result<(), MegaSenaError> {
+    println!("Comando arquivar_aposta: id={}", id);
+    db.executar(move |db| db.arquivar_aposta(id))?
+        .map_err(|e| erro_aposta(id, e))?;
+    aposta_alterada(&app, id);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn desarquivar_aposta(app: AppHandle, db: State<'_, DbActor>, id: i64) -> Result<(), MegaSenaError> {
+    println!("Comando desarquivar_aposta: id={}", id);
+    db.executar(move |db| db.desarquivar_aposta(id))?
+        .map_err(|e| erro_aposta(id, e))?;
+    aposta_alterada(&app, id);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn restaurar_aposta(app: AppHandle, db: State<'_, DbActor>, id: i64) -> Result<(), MegaSenaError> {
+    println!("Comando restaurar_aposta: id={}", id);
+    db.executar(move |db| db.restaurar_aposta(id))?
+        .map_err(|e| erro_aposta(id, e))?;
+    aposta_alterada(&app, id);
+    Ok(())
+}
+
+/// Aposta silenciada continua sendo conferida, mas não gera notificações
+#[tauri::command]
+pub fn silenciar_aposta(
+    app: AppHandle,
+    db: State<'_, DbActor>,
+    id: i64,
+    silenciada: bool,
+) -> Result<(), MegaSenaError> {
+    println!("Comando silenciar_aposta: id={}, silenciada={}", id, silenciada);
+    db.executar(move |db| db.silenciar_aposta(id, silenciada))?
+        .map_err(|e| erro_aposta(id, e))?;
+    aposta_alterada(&app, id);
+    Ok(())
+}
 
+#[tauri::command]
+pub fn obter_retencao_lixeira(db: State<'_, DbActor>) -> Result<i64, MegaSenaError> {
+    db.executar(|db| db.obter_retencao_lixeira())?
+        .map_err(MegaSenaError::from)
+}
 
+#[tauri::command]
+pub fn definir_retencao_lixeira(db: State<'_, DbActor>, dias: i64) -> Result<(), MegaSenaError> {
+    println!("Comando definir_retencao_lixeira: dias={}", dias);
+    if dias < 1 {
+        return Err(MegaSenaError::Validacao(
+            "A retenção da lixeira deve ser de pelo menos 1 dia".to_string(),
+        ));
+    }
+    db.executar(move |db| db.definir_retencao_lixeira(dias))?
+        .map_err(MegaSenaError::from)
+}
 
 #[tauri::command]
-pub fn verificar_resultados(
-    db: State<'_, Mutex<Database>>,
+pub fn obter_preferencias_notificacao(
+    db: State<'_, DbActor>,
+) -> Result<PreferenciasNotificacao, MegaSenaError> {
+    db.executar(|db| db.obter_preferencias_notificacao())?
+        .map_err(MegaSenaError::from)
+}
+
+#[tauri::command]
+pub fn definir_preferencias_notificacao(
+    db: State<'_, DbActor>,
+    preferencias: PreferenciasNotificacao,
+) -> Result<(), MegaSenaError> {
+    println!("Comando definir_preferencias_notificacao: {:?}", preferencias);
+    notificacoes::validar_preferencias(&preferencias)?;
+    db.executar(move |db| db.definir_preferencias_notificacao(&preferencias))?
+        .map_err(MegaSenaError::from)
+}
+
+/// Resultado do concurso, do cache ou, na falta dele, dos provedores. A
+/// busca corre fora da thread principal e da thread do banco.
+#[tauri::command]
+pub async fn verificar_resultados(
+    app: AppHandle,
+    db: State<'_, DbActor>,
+    providers: State<'_, ProviderChain>,
     concurso: i32,
-) -> Result<Resultado, String> {
+) -> Result<Resultado, MegaSenaError> {
     println!("Comando verificar_resultados: concurso={}", concurso);
 
     // 1) Tentar cache local primeiro (offline-first)
-    {
-        let db_lock = db.lock().map_err(|e| e.to_string())?;
-        if let Ok(Some(cached)) = db_lock.obter_resultado(concurso) {
-            // Garantir que acertos estejam atualizados para apostas recentes
-            db_lock
-                .processar_acertos_concurso(concurso, &cached.numeros_sorteados)
-                .map_err(|e| e.to_string())?;
-            return Ok(cached);
-        }
+    let cached = db
+        .executar_async(move |db| -> rusqlite::Result<Option<Resultado>> {
+            let cached = db.obter_resultado(concurso)?;
+            if cached.is_some() {
+                // Garantir que acertos estejam atualizados para apostas recentes
+                db.processar_acertos_concurso(concurso)?;
+            }
+            Ok(cached)
+        })
+        .await??;
+    if let Some(cached) = cached {
+        return Ok(cached);
     }
 
     // 2) Se não houver cache, buscar na API
-    let resultado = api::verificar_resultado(concurso)?;
+    let chain = providers.inner().clone();
+    let buscado = em_segundo_plano(move || chain.buscar_verificado(concurso)).await?;
 
     // 3) Persistir e processar acertos
-    let db_lock = db.lock().map_err(|e| e.to_string())?;
-    db_lock
-        .salvar_resultado(&resultado)
-        .map_err(|e| e.to_string())?;
-    db_lock
-        .processar_acertos_concurso(concurso, &resultado.numeros_sorteados)
-        .map_err(|e| e.to_string())?;
+    let gravado = buscado.clone();
+    let Some(eventos) = db
+        .executar_async(move |db| sincronizacao::gravar_buscado(db, &gravado))
+        .await??
+    else {
+        return Err(MegaSenaError::Conflito {
+            concurso,
+            divergencias: buscado
+                .verificacao
+                .map(|v| v.divergencias)
+                .unwrap_or_default(),
+        });
+    };
+    eventos.iter().for_each(|evento| evento.emitir(&app));
 
-    Ok(resultado)
+    Ok(buscado.resultado)
 }
 
+/// Carrega `quantidade` concursos terminando em `concurso_final`, do mais
+/// recente para o mais antigo. O banco só é usado na leitura do cache e na
+/// gravação; as buscas na API correm em paralelo, fora da thread do banco.
 #[tauri::command]
-pub fn carregar_ultimos_resultados(
-    db: State<'_, Mutex<Database>>,
+pub async fn carregar_ultimos_resultados(
+    app: AppHandle,
+    db: State<'_, DbActor>,
+    providers: State<'_, ProviderChain>,
     concurso_final: i32,
     quantidade: i32,
-) -> Result<Vec<Resultado>, String> {
-    let mut resultados = Vec::new();
+) -> Result<Vec<Resultado>, MegaSenaError> {
     let concurso_inicial = concurso_final - quantidade + 1;
-    
+    let concursos: Vec<i32> = (concurso_inicial..=concurso_final).rev().collect();
+
     println!("Comando carregar_ultimos_resultados: {} concursos a partir de {}", quantidade, concurso_final);
-    
-    for concurso in (concurso_inicial..=concurso_final).rev() {
-        // 1) Cache local
-        if let Ok(Some(cached)) = db
-            .lock()
-            .map_err(|e| e.to_string())?
-            .obter_resultado(concurso)
-        {
-            resultados.push(cached);
-            continue;
-        }
 
-        // 2) API
-        match api::verificar_resultado(concurso) {
-            Ok(resultado) => {
-                let db_lock = db.lock().map_err(|e| e.to_string())?;
-                let _ = db_lock.salvar_resultado(&resultado);
-                let _ = db_lock.processar_acertos_concurso(concurso, &resultado.numeros_sorteados);
-                resultados.push(resultado);
+    // 1) Cache local
+    let procurados = concursos.clone();
+    let mut em_cache = db
+        .executar_async(move |db| -> rusqlite::Result<HashMap<i32, Resultado>> {
+            let mut em_cache = HashMap::new();
+            for concurso in procurados {
+                if let Some(cached) = db.obter_resultado(concurso)? {
+                    em_cache.insert(concurso, cached);
+                }
             }
+            Ok(em_cache)
+        })
+        .await??;
+
+    // 2) API, em paralelo e sem ocupar o banco
+    let faltantes: Vec<i32> = concursos
+        .iter()
+        .copied()
+        .filter(|c| !em_cache.contains_key(c))
+        .collect();
+    let buscados = providers.buscar_resultados(&faltantes).await;
+
+    // 3) Persistir e processar acertos
+    let mut recebidos = Vec::new();
+    for (concurso, resultado) in buscados {
+        match resultado {
+            Ok(buscado) => recebidos.push(buscado),
             Err(e) => {
                 eprintln!("Aviso: Concurso {} ainda não disponível: {}", concurso, e);
             }
         }
     }
-    
-    Ok(resultados)
+    let (salvos, eventos, conflitos) = db
+        .executar_async(move |db| -> rusqlite::Result<_> {
+            let mut salvos = Vec::new();
+            let mut eventos = Vec::new();
+            let mut conflitos = Vec::new();
+            for buscado in recebidos {
+                match sincronizacao::gravar_buscado(db, &buscado)? {
+                    Some(gravados) => {
+                        eventos.extend(gravados);
+                        salvos.push(buscado.resultado);
+                    }
+                    None => conflitos.push(buscado),
+                }
+            }
+            Ok((salvos, eventos, conflitos))
+        })
+        .await??;
+    eventos.iter().for_each(|evento| evento.emitir(&app));
+    // Provedores divergem: o concurso fica fora da lista e o frontend é avisado
+    for buscado in conflitos {
+        eprintln!(
+            "Aviso: Concurso {} não salvo: as fontes divergem",
+            buscado.resultado.concurso
+        );
+        if let Some(verificacao) = buscado.verificacao {
+            let _ = app.emit("resultado-divergente", verificacao);
+        }
+    }
+    em_cache.extend(salvos.into_iter().map(|resultado| (resultado.concurso, resultado)));
+
+    Ok(concursos
+        .iter()
+        .filter_map(|c| em_cache.remove(c))
+        .collect())
 }
 
 #[tauri::command]
-pub fn obter_ultimo_concurso() -> Result<i32, String> {
-    api::obter_ultimo_concurso_numero()
+pub fn obter_premios_aposta(
+    db: State<'_, DbActor>,
+    aposta_id: i64,
+) -> Result<Vec<PremioAposta>, MegaSenaError> {
+    db.executar(move |db| db.obter_premios_aposta(aposta_id))?
+        .map_err(MegaSenaError::from)
 }
 
-/// Calcular acertos entre números apostados e sorteados
-fn calcular_acertos(numeros_aposta: &[i32], numeros_sorteados: &[i32]) -> i32 {
-    numeros_aposta
-        .iter()
-        .filter(|n| numeros_sorteados.contains(n))
-        .count() as i32
+/// Próximo sorteio segundo o último resultado publicado. Sem resposta dos
+/// provedores, usa o concurso mais recente em cache, adiantado pelo
+/// calendário de sorteios se o cache estiver defasado. Faz requisições:
+/// não chamar da thread principal.
+pub fn consultar_proximo_concurso(
+    db: &DbActor,
+    providers: &ProviderChain,
+) -> Result<ProximoConcurso, MegaSenaError> {
+    let erro = match providers.buscar_ultimo() {
+        Ok(resultado) => return Ok(api::proximo_concurso(&resultado)),
+        Err(e) => e,
+    };
+    eprintln!("Usando cache e calendário para o próximo concurso: {}", erro);
+
+    let cache = db.executar(|db| db.obter_ultimo_resultado())??;
+    Ok(proximo_pelo_calendario(cache, chrono::Utc::now()))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Próximo sorteio a partir do concurso mais recente em cache, adiantado
+/// pelo calendário de sorteios se o cache estiver defasado
+fn proximo_pelo_calendario(
+    cache: Option<Resultado>,
+    agora: chrono::DateTime<chrono::Utc>,
+) -> ProximoConcurso {
+    let referencia = calendario::melhor_referencia(
+        cache
+            .as_ref()
+            .and_then(|r| calendario::Referencia::nova(r.concurso, &r.data_sorteio)),
+    );
+    let estimado = calendario::estimar_concurso_atual(referencia, agora) + 1;
 
-    #[test]
-    fn test_calcular_acertos_sena() {
-        let aposta = vec![1, 2, 3, 4, 5, 6];
-        let sorteio = vec![1, 2, 3, 4, 5, 6];
-        assert_eq!(calcular_acertos(&aposta, &sorteio), 6);
+    match cache.map(|r| api::proximo_concurso(&r)) {
+        Some(proximo) if proximo.concurso >= estimado => proximo,
+        _ => ProximoConcurso {
+            concurso: estimado,
+            data_sorteio: Some(
+                calendario::proximo_sorteio(agora)
+                    .format("%d/%m/%Y")
+                    .to_string(),
+            ),
+            valor_estimado: None,
+            valor_acumulado: None,
+        },
     }
+}
 
-    #[test]
-    fn test_calcular_acertos_quadra() {
-        let aposta = vec![1, 2, 3, 4, 5, 6];
-        let sorteio = vec![1, 2, 3, 4, 10, 11];
-        assert_eq!(calcular_acertos(&aposta, &sorteio), 4);
+/// Executa uma consulta bloqueante aos provedores fora da thread principal
+async fn em_segundo_plano<T, F>(tarefa: F) -> Result<T, MegaSenaError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, MegaSenaError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(tarefa)
+        .await
+        .map_err(|e| MegaSenaError::Rede(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn obter_proximo_concurso(
+    db: State<'_, DbActor>,
+    providers: State<'_, ProviderChain>,
+) -> Result<ProximoConcurso, MegaSenaError> {
+    let chain = providers.inner().clone();
+    match em_segundo_plano(move || chain.buscar_ultimo()).await {
+        Ok(resultado) => Ok(api::proximo_concurso(&resultado)),
+        Err(erro) => {
+            eprintln!("Usando cache e calendário para o próximo concurso: {}", erro);
+            let cache = db.executar_async(|db| db.obter_ultimo_resultado()).await??;
+            Ok(proximo_pelo_calendario(cache, chrono::Utc::now()))
+        }
     }
+}
 
-    #[test]
-    fn test_calcular_acertos_zero() {
-        let aposta = vec![1, 2, 3, 4, 5, 6];
-        let sorteio = vec![10, 11, 12, 13, 14, 15];
-        assert_eq!(calcular_acertos(&aposta, &sorteio), 0);
+#[tauri::command]
+pub async fn obter_ultimo_concurso(
+    db: State<'_, DbActor>,
+    providers: State<'_, ProviderChain>,
+) -> Result<i32, MegaSenaError> {
+    // Último resultado em cache: referência do calendário quando offline
+    let cache = db.executar_async(|db| db.obter_ultimo_resultado()).await??;
+    let chain = providers.inner().clone();
+    em_segundo_plano(move || chain.obter_ultimo_concurso_numero(cache.as_ref())).await
+}
+
+/// Busca todos os concursos já sorteados que ainda faltam para as apostas
+/// e processa os acertos de cada um
+#[tauri::command]
+pub async fn sincronizar_resultados(
+    app: AppHandle,
+    db: State<'_, DbActor>,
+    providers: State<'_, ProviderChain>,
+) -> Result<ResumoSincronizacao, MegaSenaError> {
+    println!("Comando sincronizar_resultados");
+    let resumo = sincronizacao::sincronizar(&db, &providers, &|evento| evento.emitir(&app)).await?;
+    if !resumo.novos.is_empty() {
+        let _ = app.emit("novo-resultado", ());
     }
+    Ok(resumo)
+}
+
+/// Confere com a Caixa os resultados obtidos de fontes alternativas
+#[tauri::command]
+pub async fn reconciliar_resultados(
+    app: AppHandle,
+    db: State<'_, DbActor>,
+    providers: State<'_, ProviderChain>,
+) -> Result<ResumoReconciliacao, MegaSenaError> {
+    let resumo =
+        reconciliacao::reconciliar_provisorios(&db, &providers, &|evento| evento.emitir(&app)).await?;
+    if !resumo.corrigidos.is_empty() {
+        let _ = app.emit("novo-resultado", ());
+    }
+    Ok(resumo)
+}
+
+/// Verificações cruzadas registradas (com `somente_conflitos`, só as divergentes)
+#[tauri::command]
+pub fn listar_verificacoes(
+    db: State<'_, DbActor>,
+    somente_conflitos: Option<bool>,
+) -> Result<Vec<VerificacaoResultado>, MegaSenaError> {
+    let somente_conflitos = somente_conflitos.unwrap_or(false);
+    db.executar(move |db| db.listar_verificacoes(somente_conflitos))?
+        .map_err(MegaSenaError::from)
+}
+
+/// Estado dos disjuntores dos provedores de resultados
+#[tauri::command]
+pub fn diagnostico_provedores(providers: State<'_, ProviderChain>) -> Vec<DiagnosticoProvedor> {
+    providers.diagnostico()
+}
+
+/// Progresso enviado ao frontend no evento `importacao-progresso`
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProgressoImportacao {
+    processados: usize,
+    total: usize,
+}
+
+#[tauri::command]
+pub async fn importar_resultados(
+    app: AppHandle,
+    db: State<'_, DbActor>,
+    caminho: String,
+) -> Result<ResumoImportacao, MegaSenaError> {
+    println!("Comando importar_resultados: {}", caminho);
+    importacao::importar_arquivo(&db, caminho.into(), &|processados, total| {
+        let _ = app.emit("importacao-progresso", ProgressoImportacao { processados, total });
+    })
+    .await
+}
+
+/// Reinterpreta as respostas guardadas dos provedores, sem acessar a rede
+#[tauri::command]
+pub async fn reprocessar_resultados(
+    app: AppHandle,
+    db: State<'_, DbActor>,
+) -> Result<ResumoImportacao, MegaSenaError> {
+    println!("Comando reprocessar_resultados");
+    importacao::reprocessar_respostas(&db, &|processados, total| {
+        let _ = app.emit("importacao-progresso", ProgressoImportacao { processados, total });
+    })
+    .await
+}
+
+/// Momento da próxima verificação em segundo plano (RFC 3339); `None`
+/// enquanto uma verificação está em andamento
+#[tauri::command]
+pub fn obter_proxima_verificacao(agendador: State<'_, Agendador>) -> Option<String> {
+    agendador.proxima().map(|proxima| proxima.to_rfc3339())
+}
+
+/// Antecipa a verificação em segundo plano; o desfecho chega pelos eventos
+/// de sempre (`novo-resultado`, `resultado-divergente`)
+#[tauri::command]
+pub fn verificar_agora(agendador: State<'_, Agendador>) {
+    println!("Comando verificar_agora");
+    agendador.verificar_agora();
 }
@@ -0,0 +1,183 @@
+/*
+ * MegaSena Monitor - Minimalist desktop application for managing bets.
+ * Copyright (C) 2025 Zander Cattapreta
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Application configuration (config.json in the app data dir)
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AppConfig {
+    pub api: ApiConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ApiConfig {
+    /// Provedores de resultados, na ordem de preferência definida por `priority`
+    pub providers: Vec<ProviderConfig>,
+    /// Máximo de requisições simultâneas ao carregar vários concursos
+    pub max_concurrent_requests: usize,
+    pub retry: RetryConfig,
+    pub circuit_breaker: CircuitBreakerConfig,
+    /// Consultar todos os provedores e só gravar resultados em que concordam
+    pub cross_validate: bool,
+}
+
+/// Novas tentativas para falhas transitórias (5xx, timeout, conexão)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RetryConfig {
+    /// Total de tentativas por provedor, incluindo a primeira
+    pub max_attempts: u32,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        RetryConfig {
+            max_attempts: 3,
+            base_delay_ms: 500,
+            max_delay_ms: 8_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CircuitBreakerConfig {
+    /// Falhas seguidas que tiram o provedor da cadeia
+    pub failure_threshold: u32,
+    /// Tempo fora da cadeia antes de uma nova chamada de teste
+    pub cooldown_secs: u64,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        CircuitBreakerConfig {
+            failure_threshold: 5,
+            cooldown_secs: 300,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderConfig {
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Menor valor = consultado primeiro
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Endereço alternativo da API (ex.: `http://127.0.0.1:8787/caixa` com o
+    /// `fake_loteria_server`); ausente = endereço oficial do provedor
+    #[serde(default)]
+    pub base_url: Option<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_timeout_secs() -> u64 {
+    10
+}
+
+pub fn default_max_concurrent() -> usize {
+    4
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        ApiConfig {
+            providers: vec![
+                ProviderConfig {
+                    name: "caixa".to_string(),
+                    enabled: true,
+                    priority: 0,
+                    timeout_secs: default_timeout_secs(),
+                    base_url: None,
+                },
+                ProviderConfig {
+                    name: "guidi".to_string(),
+                    enabled: true,
+                    priority: 10,
+                    timeout_secs: default_timeout_secs(),
+                    base_url: None,
+                },
+            ],
+            max_concurrent_requests: default_max_concurrent(),
+            retry: RetryConfig::default(),
+            circuit_breaker: CircuitBreakerConfig::default(),
+            cross_validate: false,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Carrega a configuração do arquivo indicado.
+    /// Arquivo ausente ou inválido resulta na configuração padrão.
+    pub fn load(path: &Path) -> Self {
+        let conteudo = match std::fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => return AppConfig::default(),
+        };
+
+        match serde_json::from_str(&conteudo) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!(
+                    "Configuração inválida em {:?}, usando padrão: {}",
+                    path, e
+                );
+                AppConfig::default()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_parcial_usa_padroes() {
+        let json = r#"{ "api": { "providers": [ { "name": "guidi", "priority": 1 } ] } }"#;
+        let config: AppConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.api.providers.len(), 1);
+        let p = &config.api.providers[0];
+        assert_eq!(p.name, "guidi");
+        assert!(p.enabled);
+        assert_eq!(p.timeout_secs, 10);
+        assert_eq!(config.api.retry.max_attempts, 3);
+        assert_eq!(config.api.circuit_breaker.failure_threshold, 5);
+    }
+
+    #[test]
+    fn test_config_arquivo_ausente() {
+        let config = AppConfig::load(Path::new("/caminho/inexistente/config.json"));
+        assert_eq!(config.api.providers.len(), 2);
+        assert_eq!(config.api.providers[0].name, "caixa");
+    }
+}
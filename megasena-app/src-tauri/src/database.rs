@@ -18,11 +18,27 @@
 
 // Database operations for MegaSena App
 
-use crate::models::Aposta;
+use crate::domain::{self, Concurso, ContagemFaixas, Dezenas, QuantidadeConcursos};
+use crate::migrations::{self, MigrationError};
+use crate::models::{
+    Aposta, FaixaPremio, FiltroApostas, PreferenciasNotificacao, PremioAposta, RespostaBruta,
+    Resultado, ResumoImportacao, StatusVerificacao, VerificacaoResultado,
+};
+use crate::notificacoes::ConferenciaAposta;
+use crate::premios;
 use rusqlite::{params, Connection, Result};
-use serde_json;
+use std::collections::HashMap;
 use std::path::PathBuf;
 
+/// Dias que uma aposta fica na lixeira antes de ser apagada de vez
+pub const RETENCAO_LIXEIRA_PADRAO: i64 = 30;
+const CHAVE_RETENCAO_LIXEIRA: &str = "lixeira_retencao_dias";
+const CHAVE_FAIXA_MINIMA: &str = "notificacoes_faixa_minima";
+const CHAVE_SILENCIO_INICIO: &str = "notificacoes_silencio_inicio";
+const CHAVE_SILENCIO_FIM: &str = "notificacoes_silencio_fim";
+const CHAVE_SEM_PREMIO: &str = "notificacoes_sem_premio";
+const CHAVE_ACUMULADO: &str = "notificacoes_acumulado";
+
 pub struct Database {
     conn: Connection,
 }
@@ -35,275 +51,1064 @@ impl Database {
         Ok(Database { conn })
     }
 
-    pub fn init(&self) -> Result<()> {
-        self.conn.execute_batch(
-            "CREATE TABLE IF NOT EXISTS apostas (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                numeros TEXT NOT NULL,
-                concurso_inicial INTEGER NOT NULL,
-                quantidade_concursos INTEGER NOT NULL,
-                data_criacao DATETIME DEFAULT CURRENT_TIMESTAMP,
-                ativa BOOLEAN DEFAULT 1
-            );
+    /// Aplica as migrações pendentes do esquema
+    pub fn init(&mut self) -> std::result::Result<(), MigrationError> {
+        migrations::migrar(&mut self.conn)
+    }
 
-            CREATE TABLE IF NOT EXISTS resultados (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                concurso INTEGER NOT NULL UNIQUE,
-                numeros_sorteados TEXT NOT NULL,
-                data_sorteio DATE,
-                acumulado BOOLEAN,
-                valor_premio REAL,
-                ganhadores INTEGER,
-                valor_total REAL,
-                data_verificacao DATETIME DEFAULT CURRENT_TIMESTAMP
-            );
+    /// Agrupa as chamadas seguintes a este banco numa transação, desfeita
+    /// se não houver `commit`. Os métodos chamados nela não podem abrir
+    /// outra transação.
+    pub fn transacao(&self) -> Result<rusqlite::Transaction<'_>> {
+        self.conn.unchecked_transaction()
+    }
+
+    pub fn adicionar_aposta(
+        &self,
+        numeros: &Dezenas,
+        concurso_inicial: Concurso,
+        quantidade_concursos: QuantidadeConcursos,
+    ) -> Result<Aposta> {
+        let tx = self.conn.unchecked_transaction()?;
 
-            CREATE TABLE IF NOT EXISTS apostas_resultados (
-                aposta_id INTEGER,
-                concurso INTEGER,
-                acertos INTEGER,
-                PRIMARY KEY (aposta_id, concurso),
-                FOREIGN KEY (aposta_id) REFERENCES apostas(id) ON DELETE CASCADE,
-                FOREIGN KEY (concurso) REFERENCES resultados(concurso) ON DELETE CASCADE
-            );",
+        tx.execute(
+            "INSERT INTO apostas (concurso_inicial, quantidade_concursos) VALUES (?1, ?2)",
+            params![concurso_inicial, quantidade_concursos],
         )?;
+        let id = tx.last_insert_rowid();
 
-        // Migrações manuais para colunas novas se a tabela já existir (ignora erro se já existirem)
-        let _ = self
-            .conn
-            .execute("ALTER TABLE resultados ADD COLUMN valor_premio REAL", []);
-        let _ = self
-            .conn
-            .execute("ALTER TABLE resultados ADD COLUMN ganhadores INTEGER", []);
-        let _ = self
-            .conn
-            .execute("ALTER TABLE resultados ADD COLUMN valor_total REAL", []);
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR IGNORE INTO aposta_dezenas (aposta_id, dezena) VALUES (?1, ?2)",
+            )?;
+            for dezena in numeros.iter() {
+                stmt.execute(params![id, dezena])?;
+            }
+        }
+        tx.commit()?;
 
-        Ok(())
+        let aposta = self
+            .carregar_apostas("a.id = ?1", &[&id])?
+            .pop()
+            .ok_or(rusqlite::Error::QueryReturnedNoRows)?;
+
+        println!("Aposta adicionada com ID: {}", aposta.id);
+
+        Ok(aposta)
     }
 
-    pub fn adicionar_aposta(
+    /// Corrige dezenas e período de uma aposta e recalcula, na mesma transação,
+    /// os acertos a partir dos resultados já armazenados
+    pub fn atualizar_aposta(
         &self,
-        numeros: Vec<i32>,
-        concurso_inicial: i32,
-        quantidade_concursos: i32,
+        id: i64,
+        numeros: &Dezenas,
+        concurso_inicial: Concurso,
+        quantidade_concursos: QuantidadeConcursos,
     ) -> Result<Aposta> {
-        let numeros_json = serde_json::to_string(&numeros).unwrap();
+        let tx = self.conn.unchecked_transaction()?;
 
-        self.conn.execute(
-            "INSERT INTO apostas (numeros, concurso_inicial, quantidade_concursos) VALUES (?1, ?2, ?3)",
-            params![numeros_json, concurso_inicial, quantidade_concursos],
+        let alteradas = tx.execute(
+            "UPDATE apostas SET concurso_inicial = ?2, quantidade_concursos = ?3 WHERE id = ?1",
+            params![id, concurso_inicial, quantidade_concursos],
         )?;
+        if alteradas == 0 {
+            return Err(rusqlite::Error::QueryReturnedNoRows);
+        }
 
-        let id = self.conn.last_insert_rowid();
+        tx.execute(
+            "DELETE FROM aposta_dezenas WHERE aposta_id = ?1",
+            params![id],
+        )?;
+        for dezena in numeros.iter() {
+            tx.execute(
+                "INSERT OR IGNORE INTO aposta_dezenas (aposta_id, dezena) VALUES (?1, ?2)",
+                params![id, dezena],
+            )?;
+        }
 
-        let mut stmt = self.conn.prepare(
-            "SELECT id, numeros, concurso_inicial, quantidade_concursos,
-             datetime(data_criacao) as data_criacao, ativa
-             FROM apostas WHERE id = ?1",
-        )?;
-
-        let aposta = stmt.query_row(params![id], |row| {
-            let id: i64 = row.get(0)?;
-            let numeros_str: String = row.get(1)?;
-            let numeros: Vec<i32> = serde_json::from_str(&numeros_str).unwrap_or_else(|_| {
-                numeros_str
-                    .split(',')
-                    .filter_map(|s| s.trim().parse().ok())
-                    .collect()
-            });
+        tx.execute(
+            "DELETE FROM apostas_resultados WHERE aposta_id = ?1",
+            params![id],
+        )?;
+        tx.execute(
+            "DELETE FROM apostas_premios WHERE aposta_id = ?1",
+            params![id],
+        )?;
 
-            // Buscar resultados/acertos
-            let acertos = self.obter_acertos_aposta(id).unwrap_or_default();
+        Self::recalcular_aposta(&tx, id)?;
 
-            // Buscar os números sorteados de cada concurso verificado
-            let mut resultados_concursos = std::collections::HashMap::new();
-            for (&concurso, _) in &acertos {
-                if let Ok(Some(res)) = self.obter_resultado(concurso) {
-                    resultados_concursos.insert(concurso, res.numeros_sorteados);
-                }
+        tx.commit()?;
+        println!("Aposta {} atualizada", id);
+
+        self.carregar_apostas("a.id = ?1", &[&id])?
+            .pop()
+            .ok_or(rusqlite::Error::QueryReturnedNoRows)
+    }
+
+    pub fn listar_apostas(&self) -> Result<Vec<Aposta>> {
+        self.listar_apostas_filtro(FiltroApostas::Ativas)
+    }
+
+    pub fn listar_apostas_filtro(&self, filtro: FiltroApostas) -> Result<Vec<Aposta>> {
+        let condicao = match filtro {
+            FiltroApostas::Ativas => "a.ativa = 1 AND a.excluida_em IS NULL",
+            FiltroApostas::Arquivadas => "a.ativa = 0 AND a.excluida_em IS NULL",
+            FiltroApostas::Finalizadas => {
+                "a.excluida_em IS NULL
+                 AND a.concurso_inicial + a.quantidade_concursos - 1
+                     <= (SELECT COALESCE(MAX(concurso), 0) FROM resultados)"
             }
+            FiltroApostas::Lixeira => "a.excluida_em IS NOT NULL",
+            FiltroApostas::Todas => "a.excluida_em IS NULL",
+        };
+        self.carregar_apostas(condicao, &[])
+    }
 
-            Ok(Aposta {
+    /// Carrega as apostas que satisfazem `condicao` (sobre o alias `a` de
+    /// `apostas`) com dezenas, acertos e sorteios em um número fixo de
+    /// consultas, independente da quantidade de apostas.
+    fn carregar_apostas(
+        &self,
+        condicao: &str,
+        parametros: &[&dyn rusqlite::ToSql],
+    ) -> Result<Vec<Aposta>> {
+        // 1) Dezenas apostadas
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT ad.aposta_id, ad.dezena
+             FROM aposta_dezenas ad
+             JOIN apostas a ON a.id = ad.aposta_id
+             WHERE {}
+             ORDER BY ad.aposta_id, ad.dezena",
+            condicao
+        ))?;
+        let mut dezenas: HashMap<i64, Vec<i32>> = HashMap::new();
+        let mut rows = stmt.query(parametros)?;
+        while let Some(row) = rows.next()? {
+            dezenas.entry(row.get(0)?).or_default().push(row.get(1)?);
+        }
+
+        // 2) Apostas
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT a.id, a.concurso_inicial, a.quantidade_concursos,
+             datetime(a.data_criacao) as data_criacao, a.ativa, datetime(a.excluida_em),
+             a.silenciada
+             FROM apostas a
+             WHERE {}
+             ORDER BY a.id DESC",
+            condicao
+        ))?;
+        // Uma aposta com dezenas inválidas não impede a listagem das demais
+        let mut apostas = Vec::new();
+        let mut rows = stmt.query(parametros)?;
+        while let Some(row) = rows.next()? {
+            let id = row.get(0)?;
+            let numeros = match Dezenas::novas(dezenas.remove(&id).unwrap_or_default()) {
+                Ok(numeros) => numeros,
+                Err(e) => {
+                    eprintln!("Aposta {} ignorada: {}", id, e);
+                    continue;
+                }
+            };
+            apostas.push(Aposta {
                 id,
                 numeros,
-                concurso_inicial: row.get(2)?,
-                quantidade_concursos: row.get(3)?,
-                data_criacao: row.get(4)?,
-                ativa: row.get(5)?,
-                acertos,
-                resultados_concursos,
-            })
-        })?;
+                concurso_inicial: row.get(1)?,
+                quantidade_concursos: row.get(2)?,
+                data_criacao: row.get(3)?,
+                ativa: row.get(4)?,
+                excluida_em: row.get(5)?,
+                silenciada: row.get(6)?,
+                acertos: HashMap::new(),
+                premiacoes: HashMap::new(),
+                resultados_concursos: HashMap::new(),
+            });
+        }
 
-        println!("Aposta adicionada com ID: {}", aposta.id);
+        if apostas.is_empty() {
+            return Ok(apostas);
+        }
+        let indices: HashMap<i64, usize> = apostas
+            .iter()
+            .enumerate()
+            .map(|(i, aposta)| (aposta.id, i))
+            .collect();
 
-        Ok(aposta)
+        // 3) Acertos e contagem por faixa
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT ar.aposta_id, ar.concurso, ar.acertos, ar.senas, ar.quinas, ar.quadras
+             FROM apostas_resultados ar
+             JOIN apostas a ON a.id = ar.aposta_id
+             WHERE {}",
+            condicao
+        ))?;
+        let mut rows = stmt.query(parametros)?;
+        while let Some(row) = rows.next()? {
+            if let Some(&i) = indices.get(&row.get::<_, i64>(0)?) {
+                let concurso: i32 = row.get(1)?;
+                apostas[i].acertos.insert(concurso, row.get(2)?);
+                apostas[i].premiacoes.insert(
+                    concurso,
+                    ContagemFaixas {
+                        senas: row.get(3)?,
+                        quinas: row.get(4)?,
+                        quadras: row.get(5)?,
+                    },
+                );
+            }
+        }
+
+        // 4) Números sorteados de cada concurso verificado
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT rd.concurso, rd.dezena
+             FROM resultado_dezenas rd
+             WHERE rd.concurso IN (
+                SELECT DISTINCT ar.concurso
+                FROM apostas_resultados ar
+                JOIN apostas a ON a.id = ar.aposta_id
+                WHERE {}
+             )
+             ORDER BY rd.concurso, rd.dezena",
+            condicao
+        ))?;
+        let mut sorteios: HashMap<i32, Vec<i32>> = HashMap::new();
+        let mut rows = stmt.query(parametros)?;
+        while let Some(row) = rows.next()? {
+            sorteios.entry(row.get(0)?).or_default().push(row.get(1)?);
+        }
+
+        for aposta in &mut apostas {
+            for concurso in aposta.acertos.keys() {
+                if let Some(numeros) = sorteios.get(concurso) {
+                    aposta
+                        .resultados_concursos
+                        .insert(*concurso, numeros.clone());
+                }
+            }
+        }
+
+        Ok(apostas)
     }
 
-    pub fn listar_apostas(&self) -> Result<Vec<Aposta>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, numeros, concurso_inicial, quantidade_concursos,
-             datetime(data_criacao) as data_criacao, ativa
-             FROM apostas
-             WHERE ativa = 1
-             ORDER BY id DESC",
+    pub fn obter_dezenas_aposta(&self, aposta_id: i64) -> Result<Vec<i32>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT dezena FROM aposta_dezenas WHERE aposta_id = ?1 ORDER BY dezena",
         )?;
+        let dezenas = stmt
+            .query_map(params![aposta_id], |row| row.get(0))?
+            .collect::<Result<Vec<i32>>>()?;
+        Ok(dezenas)
+    }
 
-        let apostas = stmt
-            .query_map([], |row| {
-                let id: i64 = row.get(0)?;
-                let numeros_str: String = row.get(1)?;
-                let numeros: Vec<i32> = serde_json::from_str(&numeros_str).unwrap_or_else(|_| {
-                    numeros_str
-                        .split(',')
-                        .filter_map(|s| s.trim().parse().ok())
-                        .collect()
-                });
-
-                // Buscar resultados/acertos
-                let acertos = self.obter_acertos_aposta(id).unwrap_or_default();
-
-                // Buscar os números sorteados de cada concurso verificado
-                let mut resultados_concursos = std::collections::HashMap::new();
-                for (&concurso, _) in &acertos {
-                    if let Ok(Some(res)) = self.obter_resultado(concurso) {
-                        resultados_concursos.insert(concurso, res.numeros_sorteados);
-                    }
-                }
+    pub fn obter_dezenas_resultado(&self, concurso: i32) -> Result<Vec<i32>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT dezena FROM resultado_dezenas WHERE concurso = ?1 ORDER BY dezena",
+        )?;
+        let dezenas = stmt
+            .query_map(params![concurso], |row| row.get(0))?
+            .collect::<Result<Vec<i32>>>()?;
+        Ok(dezenas)
+    }
 
-                Ok(Aposta {
-                    id,
-                    numeros,
-                    concurso_inicial: row.get(2)?,
-                    quantidade_concursos: row.get(3)?,
-                    data_criacao: row.get(4)?,
-                    ativa: row.get(5)?,
-                    acertos,
-                    resultados_concursos,
-                })
+    /// Recalcula os acertos da aposta para todos os resultados já armazenados
+    /// do seu período
+    fn recalcular_aposta(conn: &Connection, id: i64) -> Result<()> {
+        let mut stmt = conn.prepare(
+            "SELECT r.concurso FROM resultados r
+             JOIN apostas a ON a.id = ?1
+             WHERE r.concurso >= a.concurso_inicial
+             AND r.concurso < a.concurso_inicial + a.quantidade_concursos
+             ORDER BY r.concurso",
+        )?;
+        let concursos = stmt
+            .query_map(params![id], |row| row.get::<_, i32>(0))?
+            .collect::<Result<Vec<_>>>()?;
+
+        for concurso in concursos {
+            Self::calcular_acertos(conn, concurso, Some(id))?;
+        }
+        Ok(())
+    }
+
+    fn alterar_aposta(&self, id: i64, sql: &str) -> Result<()> {
+        let alteradas = self.conn.execute(sql, params![id])?;
+        if alteradas == 0 {
+            return Err(rusqlite::Error::QueryReturnedNoRows);
+        }
+        Ok(())
+    }
+
+    /// Arquivada: sai da lista principal, mas continua tendo acertos calculados
+    pub fn arquivar_aposta(&self, id: i64) -> Result<()> {
+        self.alterar_aposta(
+            id,
+            "UPDATE apostas SET ativa = 0 WHERE id = ?1 AND excluida_em IS NULL",
+        )
+    }
+
+    pub fn desarquivar_aposta(&self, id: i64) -> Result<()> {
+        self.alterar_aposta(
+            id,
+            "UPDATE apostas SET ativa = 1 WHERE id = ?1 AND excluida_em IS NULL",
+        )
+    }
+
+    /// Aposta silenciada continua sendo conferida, mas não notifica
+    pub fn silenciar_aposta(&self, id: i64, silenciada: bool) -> Result<()> {
+        let sql = if silenciada {
+            "UPDATE apostas SET silenciada = 1 WHERE id = ?1 AND excluida_em IS NULL"
+        } else {
+            "UPDATE apostas SET silenciada = 0 WHERE id = ?1 AND excluida_em IS NULL"
+        };
+        self.alterar_aposta(id, sql)
+    }
+
+    /// Move para a lixeira; a remoção definitiva fica para `purgar_lixeira`
+    pub fn mover_para_lixeira(&self, id: i64) -> Result<()> {
+        self.alterar_aposta(
+            id,
+            "UPDATE apostas SET excluida_em = CURRENT_TIMESTAMP
+             WHERE id = ?1 AND excluida_em IS NULL",
+        )
+    }
+
+    /// Tira da lixeira e recalcula os acertos dos concursos sorteados enquanto
+    /// a aposta estava lá
+    pub fn restaurar_aposta(&self, id: i64) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        let alteradas = tx.execute(
+            "UPDATE apostas SET excluida_em = NULL WHERE id = ?1 AND excluida_em IS NOT NULL",
+            params![id],
+        )?;
+        if alteradas == 0 {
+            return Err(rusqlite::Error::QueryReturnedNoRows);
+        }
+        Self::recalcular_aposta(&tx, id)?;
+        tx.commit()
+    }
+
+    /// Apaga de vez as apostas que estão na lixeira há mais de `retencao_dias`
+    pub fn purgar_lixeira(&self, retencao_dias: i64) -> Result<usize> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id FROM apostas
+             WHERE excluida_em IS NOT NULL
+             AND excluida_em <= datetime('now', ?1)",
+        )?;
+        let ids = stmt
+            .query_map(params![format!("-{} days", retencao_dias)], |row| {
+                row.get::<_, i64>(0)
             })?
             .collect::<Result<Vec<_>>>()?;
 
-        Ok(apostas)
+        for &id in &ids {
+            self.excluir_aposta(id)?;
+        }
+        if !ids.is_empty() {
+            println!(
+                "Lixeira: {} aposta(s) removida(s) definitivamente",
+                ids.len()
+            );
+        }
+        Ok(ids.len())
     }
-    pub fn excluir_aposta(&self, id: i64) -> Result<()> {
-        // Limpar acertos e aposta
+
+    pub fn obter_retencao_lixeira(&self) -> Result<i64> {
+        Ok(self
+            .obter_configuracao(CHAVE_RETENCAO_LIXEIRA)?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(RETENCAO_LIXEIRA_PADRAO))
+    }
+
+    pub fn definir_retencao_lixeira(&self, dias: i64) -> Result<()> {
+        self.definir_configuracao(CHAVE_RETENCAO_LIXEIRA, &dias.to_string())
+    }
+
+    /// Preferências gravadas, com o padrão no lugar das ausentes
+    pub fn obter_preferencias_notificacao(&self) -> Result<PreferenciasNotificacao> {
+        let padrao = PreferenciasNotificacao::default();
+        let texto = |chave| -> Result<Option<String>> {
+            Ok(self.obter_configuracao(chave)?.filter(|v| !v.is_empty()))
+        };
+        let booleano = |chave, padrao| -> Result<bool> {
+            Ok(texto(chave)?.map(|v| v == "1").unwrap_or(padrao))
+        };
+        Ok(PreferenciasNotificacao {
+            faixa_minima: texto(CHAVE_FAIXA_MINIMA)?
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(padrao.faixa_minima),
+            silencio_inicio: texto(CHAVE_SILENCIO_INICIO)?,
+            silencio_fim: texto(CHAVE_SILENCIO_FIM)?,
+            notificar_sem_premio: booleano(CHAVE_SEM_PREMIO, padrao.notificar_sem_premio)?,
+            notificar_acumulado: booleano(CHAVE_ACUMULADO, padrao.notificar_acumulado)?,
+        })
+    }
+
+    pub fn definir_preferencias_notificacao(
+        &self,
+        preferencias: &PreferenciasNotificacao,
+    ) -> Result<()> {
+        let booleano = |valor: bool| if valor { "1" } else { "0" };
+        let tx = self.conn.unchecked_transaction()?;
+        self.definir_configuracao(CHAVE_FAIXA_MINIMA, &preferencias.faixa_minima.to_string())?;
+        self.definir_configuracao(
+            CHAVE_SILENCIO_INICIO,
+            preferencias.silencio_inicio.as_deref().unwrap_or_default(),
+        )?;
+        self.definir_configuracao(
+            CHAVE_SILENCIO_FIM,
+            preferencias.silencio_fim.as_deref().unwrap_or_default(),
+        )?;
+        self.definir_configuracao(
+            CHAVE_SEM_PREMIO,
+            booleano(preferencias.notificar_sem_premio),
+        )?;
+        self.definir_configuracao(CHAVE_ACUMULADO, booleano(preferencias.notificar_acumulado))?;
+        tx.commit()
+    }
+
+    pub fn obter_configuracao(&self, chave: &str) -> Result<Option<String>> {
+        match self.conn.query_row(
+            "SELECT valor FROM configuracoes WHERE chave = ?1",
+            params![chave],
+            |row| row.get(0),
+        ) {
+            Ok(valor) => Ok(Some(valor)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn definir_configuracao(&self, chave: &str, valor: &str) -> Result<()> {
         self.conn.execute(
-            "DELETE FROM apostas_resultados WHERE aposta_id = ?1",
-            params![id],
+            "INSERT INTO configuracoes (chave, valor) VALUES (?1, ?2)
+             ON CONFLICT(chave) DO UPDATE SET valor = excluded.valor",
+            params![chave, valor],
         )?;
-        self.conn
-            .execute("DELETE FROM apostas WHERE id = ?1", params![id])?;
         Ok(())
     }
 
-    pub fn salvar_resultado(&self, resultado: &crate::models::Resultado) -> Result<()> {
-        let numeros_json = serde_json::to_string(&resultado.numeros_sorteados)
-            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+    /// Remove a aposta definitivamente, com acertos e prêmios
+    pub fn excluir_aposta(&self, id: i64) -> Result<()> {
+        // Limpar acertos, prêmios, dezenas e aposta, tudo ou nada
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM apostas_resultados WHERE aposta_id = ?1",
+            params![id],
+        )?;
+        tx.execute(
+            "DELETE FROM apostas_premios WHERE aposta_id = ?1",
+            params![id],
+        )?;
+        tx.execute(
+            "DELETE FROM aposta_dezenas WHERE aposta_id = ?1",
+            params![id],
+        )?;
+        tx.execute("DELETE FROM apostas WHERE id = ?1", params![id])?;
+        tx.commit()
+    }
 
+    pub fn salvar_resultado(&self, resultado: &Resultado) -> Result<()> {
         println!("Salvando resultado concurso: {}", resultado.concurso);
-        self.conn.execute(
-            "INSERT OR REPLACE INTO resultados (concurso, numeros_sorteados, data_sorteio, acumulado, valor_premio, ganhadores, valor_total)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        let tx = self.conn.unchecked_transaction()?;
+        Self::gravar_resultado(&tx, resultado)?;
+        tx.commit()
+    }
+
+    /// Grava vários resultados em uma única transação. Resultados idênticos
+    /// aos já armazenados são pulados; para os novos ou alterados os acertos
+    /// das apostas são recalculados.
+    pub fn salvar_resultados_lote(&self, resultados: &[Resultado]) -> Result<ResumoImportacao> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut resumo = ResumoImportacao {
+            total: resultados.len(),
+            ..Default::default()
+        };
+
+        for resultado in resultados {
+            // O momento da gravação e a resposta bruta não contam como diferença
+            let existente = self.obter_resultado(resultado.concurso)?.map(|mut r| {
+                r.fetched_at = resultado.fetched_at.clone();
+                r.payload = resultado.payload.clone();
+                r
+            });
+            match existente {
+                Some(existente) if existente == *resultado => resumo.inalterados += 1,
+                existente => {
+                    Self::gravar_resultado(&tx, resultado)?;
+                    Self::calcular_acertos(&tx, resultado.concurso, None)?;
+                    if existente.is_some() {
+                        resumo.atualizados += 1;
+                    } else {
+                        resumo.novos += 1;
+                    }
+                }
+            }
+        }
+
+        tx.commit()?;
+        Ok(resumo)
+    }
+
+    fn gravar_resultado(tx: &Connection, resultado: &Resultado) -> Result<()> {
+        Self::gravar_payload(tx, resultado)?;
+        // Upsert em vez de INSERT OR REPLACE: o REPLACE apagaria a linha e,
+        // em cascata, os acertos já calculados para o concurso
+        tx.execute(
+            "INSERT INTO resultados (concurso, data_sorteio, acumulado, valor_premio, ganhadores, valor_total,
+                                     fonte, fetched_at, provisional)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, COALESCE(?8, CURRENT_TIMESTAMP), ?9)
+             ON CONFLICT(concurso) DO UPDATE SET
+                data_sorteio = excluded.data_sorteio,
+                acumulado = excluded.acumulado,
+                valor_premio = excluded.valor_premio,
+                ganhadores = excluded.ganhadores,
+                valor_total = excluded.valor_total,
+                fonte = excluded.fonte,
+                fetched_at = excluded.fetched_at,
+                provisional = excluded.provisional,
+                data_verificacao = CURRENT_TIMESTAMP",
             params![
                 resultado.concurso,
-                numeros_json,
                 resultado.data_sorteio,
                 resultado.acumulado,
                 resultado.valor_premio,
                 resultado.ganhadores,
-                resultado.valor_total
+                resultado.valor_total,
+                resultado.fonte,
+                resultado.fetched_at,
+                resultado.provisional
             ],
         )?;
+
+        tx.execute(
+            "DELETE FROM resultado_dezenas WHERE concurso = ?1",
+            params![resultado.concurso],
+        )?;
+        for dezena in &resultado.numeros_sorteados {
+            tx.execute(
+                "INSERT OR IGNORE INTO resultado_dezenas (concurso, dezena) VALUES (?1, ?2)",
+                params![resultado.concurso, dezena],
+            )?;
+        }
+
+        tx.execute(
+            "DELETE FROM resultados_faixas WHERE concurso = ?1",
+            params![resultado.concurso],
+        )?;
+        for faixa in &resultado.faixas {
+            tx.execute(
+                "INSERT INTO resultados_faixas (concurso, faixa, acertos, ganhadores, valor_premio)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    resultado.concurso,
+                    faixa.faixa,
+                    faixa.acertos,
+                    faixa.ganhadores,
+                    faixa.valor_premio
+                ],
+            )?;
+        }
+
         Ok(())
     }
 
+    /// Guarda a resposta bruta do provedor, substituindo a anterior do mesmo
+    /// concurso e fonte. Resultados sem `payload` (planilha, reprocessamento)
+    /// não mexem na tabela.
+    fn gravar_payload(conn: &Connection, resultado: &Resultado) -> Result<()> {
+        let (Some(fonte), Some(payload)) = (&resultado.fonte, &resultado.payload) else {
+            return Ok(());
+        };
+        conn.execute(
+            "INSERT INTO resultados_raw (concurso, fonte, payload, fetched_at)
+             VALUES (?1, ?2, ?3, COALESCE(?4, CURRENT_TIMESTAMP))
+             ON CONFLICT(concurso, fonte) DO UPDATE SET
+                payload = excluded.payload,
+                fetched_at = excluded.fetched_at",
+            params![resultado.concurso, fonte, payload, resultado.fetched_at],
+        )?;
+        Ok(())
+    }
+
+    pub fn obter_payload(&self, concurso: i32, fonte: &str) -> Result<Option<String>> {
+        match self.conn.query_row(
+            "SELECT payload FROM resultados_raw WHERE concurso = ?1 AND fonte = ?2",
+            params![concurso, fonte],
+            |row| row.get(0),
+        ) {
+            Ok(payload) => Ok(Some(payload)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Resposta bruta de cada resultado gravado, da mesma fonte que o
+    /// originou (resultados da planilha não têm)
+    pub fn listar_respostas_brutas(&self) -> Result<Vec<RespostaBruta>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT r.concurso, r.fonte, p.payload, datetime(p.fetched_at), r.provisional
+             FROM resultados r
+             JOIN resultados_raw p ON p.concurso = r.concurso AND p.fonte = r.fonte
+             ORDER BY r.concurso",
+        )?;
+        let respostas = stmt
+            .query_map([], |row| {
+                Ok(RespostaBruta {
+                    concurso: row.get(0)?,
+                    fonte: row.get(1)?,
+                    payload: row.get(2)?,
+                    fetched_at: row.get(3)?,
+                    provisional: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(respostas)
+    }
+
+    /// Concursos gravados a partir de fonte não oficial, do mais antigo ao mais recente
+    pub fn listar_resultados_provisorios(&self) -> Result<Vec<i32>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT concurso FROM resultados WHERE provisional = 1 ORDER BY concurso")?;
+        let concursos = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<i32>>>()?;
+        Ok(concursos)
+    }
+
+    /// Apostas fora da lixeira premiadas no concurso, com a contagem por faixa
+    pub fn listar_premiacoes_concurso(&self, concurso: i32) -> Result<Vec<(i64, ContagemFaixas)>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT ar.aposta_id, ar.senas, ar.quinas, ar.quadras
+             FROM apostas_resultados ar
+             JOIN apostas a ON a.id = ar.aposta_id
+             WHERE ar.concurso = ?1
+             AND a.excluida_em IS NULL
+             AND ar.senas + ar.quinas + ar.quadras > 0
+             ORDER BY ar.aposta_id",
+        )?;
+        let premiacoes = stmt
+            .query_map(params![concurso], |row| {
+                Ok((
+                    row.get(0)?,
+                    ContagemFaixas {
+                        senas: row.get(1)?,
+                        quinas: row.get(2)?,
+                        quadras: row.get(3)?,
+                    },
+                ))
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(premiacoes)
+    }
+
+    /// Conferência de cada aposta fora da lixeira no concurso, premiada ou não
+    pub fn listar_conferencias_concurso(&self, concurso: i32) -> Result<Vec<ConferenciaAposta>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT ar.aposta_id, ar.acertos, ar.senas, ar.quinas, ar.quadras, a.silenciada
+             FROM apostas_resultados ar
+             JOIN apostas a ON a.id = ar.aposta_id
+             WHERE ar.concurso = ?1
+             AND a.excluida_em IS NULL
+             ORDER BY ar.aposta_id",
+        )?;
+        let conferencias = stmt
+            .query_map(params![concurso], |row| {
+                Ok(ConferenciaAposta {
+                    aposta_id: row.get(0)?,
+                    acertos: row.get(1)?,
+                    contagem: ContagemFaixas {
+                        senas: row.get(2)?,
+                        quinas: row.get(3)?,
+                        quadras: row.get(4)?,
+                    },
+                    silenciada: row.get(5)?,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(conferencias)
+    }
+
+    /// Registra o concurso para notificação. Um concurso já registrado não
+    /// volta para a fila, o que evita notificar duas vezes o mesmo sorteio.
+    pub fn enfileirar_notificacao(&self, concurso: i32) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO notificacoes_concursos (concurso) VALUES (?1)",
+            params![concurso],
+        )?;
+        Ok(())
+    }
+
+    /// Devolve à fila um concurso já registrado cujo resultado foi corrigido,
+    /// para que a notificação saia de novo com os dados certos
+    pub fn reenfileirar_notificacao(&self, concurso: i32) -> Result<()> {
+        self.conn.execute(
+            "UPDATE notificacoes_concursos SET enviada_em = NULL WHERE concurso = ?1",
+            params![concurso],
+        )?;
+        Ok(())
+    }
+
+    /// Concursos registrados cuja notificação ainda não foi enviada
+    pub fn listar_notificacoes_pendentes(&self) -> Result<Vec<i32>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT concurso FROM notificacoes_concursos
+             WHERE enviada_em IS NULL
+             ORDER BY concurso",
+        )?;
+        let concursos = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<i32>>>()?;
+        Ok(concursos)
+    }
+
+    pub fn marcar_notificacao_enviada(&self, concurso: i32) -> Result<()> {
+        self.conn.execute(
+            "UPDATE notificacoes_concursos SET enviada_em = CURRENT_TIMESTAMP
+             WHERE concurso = ?1",
+            params![concurso],
+        )?;
+        Ok(())
+    }
+
+    /// Concursos até `ate` cobertos por apostas fora da lixeira (inclusive
+    /// arquivadas) que ainda não têm resultado gravado, em ordem crescente
+    pub fn listar_concursos_pendentes(&self, ate: i32) -> Result<Vec<i32>> {
+        let mut stmt = self.conn.prepare(
+            "WITH RECURSIVE cobertos(concurso, fim) AS (
+                SELECT concurso_inicial, concurso_inicial + quantidade_concursos - 1
+                FROM apostas
+                WHERE excluida_em IS NULL
+                UNION
+                SELECT concurso + 1, fim FROM cobertos WHERE concurso < fim
+             )
+             SELECT DISTINCT concurso FROM cobertos
+             WHERE concurso <= ?1
+             AND concurso NOT IN (SELECT concurso FROM resultados)
+             ORDER BY concurso",
+        )?;
+        let concursos = stmt
+            .query_map(params![ate], |row| row.get(0))?
+            .collect::<Result<Vec<i32>>>()?;
+        Ok(concursos)
+    }
+
+    /// Substitui um resultado provisório pelo oficial. Devolve as diferenças
+    /// encontradas (vazio = confirmado); se as dezenas mudaram, os acertos
+    /// das apostas são recalculados.
+    pub fn reconciliar_resultado(&self, oficial: &Resultado) -> Result<Vec<String>> {
+        let anterior = self.obter_resultado(oficial.concurso)?;
+        let mut diferencas = Vec::new();
+
+        if let Some(anterior) = &anterior {
+            let fonte = anterior.fonte.as_deref().unwrap_or("desconhecida");
+            if anterior.numeros_sorteados != oficial.numeros_sorteados {
+                diferencas.push(format!(
+                    "dezenas: {} {:?}, oficial {:?}",
+                    fonte, anterior.numeros_sorteados, oficial.numeros_sorteados
+                ));
+            }
+            if anterior.ganhadores != oficial.ganhadores {
+                diferencas.push(format!(
+                    "ganhadores da Sena: {} {:?}, oficial {:?}",
+                    fonte, anterior.ganhadores, oficial.ganhadores
+                ));
+            }
+            if anterior.valor_premio != oficial.valor_premio {
+                diferencas.push(format!(
+                    "prêmio da Sena: {} {:?}, oficial {:?}",
+                    fonte, anterior.valor_premio, oficial.valor_premio
+                ));
+            }
+            if anterior.faixas != oficial.faixas {
+                diferencas.push(format!("rateio das faixas difere de {}", fonte));
+            }
+        }
+
+        for diferenca in &diferencas {
+            println!(
+                "Reconciliação do concurso {}: {}",
+                oficial.concurso, diferenca
+            );
+        }
+
+        let tx = self.conn.unchecked_transaction()?;
+        Self::gravar_resultado(&tx, oficial)?;
+        let dezenas_mudaram = anterior
+            .map(|a| a.numeros_sorteados != oficial.numeros_sorteados)
+            .unwrap_or(true);
+        if dezenas_mudaram {
+            Self::calcular_acertos(&tx, oficial.concurso, None)?;
+        }
+        tx.commit()?;
+
+        Ok(diferencas)
+    }
+
+    /// Grava um resultado buscado com verificação cruzada. Em caso de
+    /// conflito entre provedores só a verificação é registrada e o resultado
+    /// fica de fora, retornando `false`.
+    pub fn salvar_resultado_verificado(
+        &self,
+        resultado: &Resultado,
+        verificacao: Option<&VerificacaoResultado>,
+    ) -> Result<bool> {
+        if let Some(verificacao) = verificacao {
+            self.registrar_verificacao(verificacao)?;
+            if verificacao.status == StatusVerificacao::Conflito {
+                eprintln!(
+                    "Concurso {} não salvo: provedores divergem ({})",
+                    verificacao.concurso,
+                    verificacao.divergencias.join("; ")
+                );
+                // A resposta fica guardada para análise mesmo sem gravar o resultado
+                Self::gravar_payload(&self.conn, resultado)?;
+                return Ok(false);
+            }
+        }
+        self.salvar_resultado(resultado)?;
+        Ok(true)
+    }
+
+    pub fn registrar_verificacao(&self, verificacao: &VerificacaoResultado) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO resultados_verificacoes (concurso, status, provedores, divergencias)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(concurso) DO UPDATE SET
+                status = excluded.status,
+                provedores = excluded.provedores,
+                divergencias = excluded.divergencias,
+                verificado_em = CURRENT_TIMESTAMP,
+                alertada_em = CASE
+                    WHEN resultados_verificacoes.status = excluded.status
+                    THEN resultados_verificacoes.alertada_em
+                END",
+            params![
+                verificacao.concurso,
+                verificacao.status.as_str(),
+                serde_json::to_string(&verificacao.provedores).unwrap_or_default(),
+                serde_json::to_string(&verificacao.divergencias).unwrap_or_default(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Última verificação de cada concurso, do mais recente para o mais antigo
+    pub fn listar_verificacoes(
+        &self,
+        somente_conflitos: bool,
+    ) -> Result<Vec<VerificacaoResultado>> {
+        let condicao = if somente_conflitos {
+            "status = 'conflito'"
+        } else {
+            "1 = 1"
+        };
+        self.carregar_verificacoes(condicao)
+    }
+
+    /// Conflitos ainda não anunciados ao usuário
+    pub fn listar_conflitos_nao_alertados(&self) -> Result<Vec<VerificacaoResultado>> {
+        self.carregar_verificacoes("status = 'conflito' AND alertada_em IS NULL")
+    }
+
+    pub fn marcar_conflito_alertado(&self, concurso: i32) -> Result<()> {
+        self.conn.execute(
+            "UPDATE resultados_verificacoes SET alertada_em = CURRENT_TIMESTAMP
+             WHERE concurso = ?1",
+            params![concurso],
+        )?;
+        Ok(())
+    }
+
+    fn carregar_verificacoes(&self, condicao: &str) -> Result<Vec<VerificacaoResultado>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT concurso, status, provedores, divergencias, datetime(verificado_em)
+             FROM resultados_verificacoes
+             WHERE {}
+             ORDER BY concurso DESC",
+            condicao
+        ))?;
+        let verificacoes = stmt
+            .query_map([], |row| {
+                let status: String = row.get(1)?;
+                let provedores: String = row.get(2)?;
+                let divergencias: String = row.get(3)?;
+                Ok(VerificacaoResultado {
+                    concurso: row.get(0)?,
+                    status: StatusVerificacao::from_db(&status)
+                        .unwrap_or(StatusVerificacao::Conflito),
+                    provedores: serde_json::from_str(&provedores).unwrap_or_default(),
+                    divergencias: serde_json::from_str(&divergencias).unwrap_or_default(),
+                    verificado_em: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(verificacoes)
+    }
+
+    pub fn obter_faixas_resultado(&self, concurso: i32) -> Result<Vec<FaixaPremio>> {
+        Self::faixas_resultado(&self.conn, concurso)
+    }
+
+    fn faixas_resultado(conn: &Connection, concurso: i32) -> Result<Vec<FaixaPremio>> {
+        let mut stmt = conn.prepare(
+            "SELECT faixa, acertos, ganhadores, valor_premio
+             FROM resultados_faixas
+             WHERE concurso = ?1
+             ORDER BY faixa",
+        )?;
+
+        let faixas = stmt
+            .query_map(params![concurso], |row| {
+                Ok(FaixaPremio {
+                    faixa: row.get(0)?,
+                    acertos: row.get(1)?,
+                    ganhadores: row.get(2)?,
+                    valor_premio: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(faixas)
+    }
+
+    /// Concurso mais recente em cache, com a resposta bruta da fonte que o originou
+    pub fn obter_ultimo_resultado(&self) -> Result<Option<Resultado>> {
+        let ultimo: Option<i32> =
+            self.conn
+                .query_row("SELECT MAX(concurso) FROM resultados", [], |row| row.get(0))?;
+        let Some(ultimo) = ultimo else {
+            return Ok(None);
+        };
+
+        let mut resultado = self.obter_resultado(ultimo)?;
+        if let Some(r) = resultado.as_mut() {
+            if let Some(fonte) = r.fonte.clone() {
+                r.payload = self.obter_payload(ultimo, &fonte)?;
+            }
+        }
+        Ok(resultado)
+    }
+
     pub fn obter_resultado(&self, concurso: i32) -> Result<Option<crate::models::Resultado>> {
         let mut stmt = self.conn.prepare(
-            "SELECT concurso, numeros_sorteados, data_sorteio, acumulado, valor_premio, ganhadores, valor_total
+            "SELECT concurso, data_sorteio, acumulado, valor_premio, ganhadores, valor_total,
+                    fonte, datetime(fetched_at), provisional
              FROM resultados
-             WHERE concurso = ?1"
+             WHERE concurso = ?1",
         )?;
 
         let resultado = stmt.query_row(params![concurso], |row| {
-            let numeros_str: String = row.get(1)?;
-            let numeros_sorteados: Vec<i32> =
-                serde_json::from_str(&numeros_str).unwrap_or_else(|_| {
-                    numeros_str
-                        .split(',')
-                        .filter_map(|s| s.trim().parse().ok())
-                        .collect()
-                });
-
             Ok(crate::models::Resultado {
                 concurso: row.get(0)?,
-                numeros_sorteados,
-                data_sorteio: row.get(2)?,
-                acumulado: row.get(3)?,
-                valor_premio: row.get(4)?,
-                ganhadores: row.get(5)?,
-                valor_total: row.get(6)?,
+                numeros_sorteados: Vec::new(),
+                data_sorteio: row.get(1)?,
+                acumulado: row.get(2)?,
+                valor_premio: row.get(3)?,
+                ganhadores: row.get(4)?,
+                valor_total: row.get(5)?,
+                faixas: Vec::new(),
+                fonte: row.get(6)?,
+                fetched_at: row.get(7)?,
+                provisional: row.get(8)?,
+                payload: None,
             })
         });
 
         match resultado {
-            Ok(r) => Ok(Some(r)),
+            Ok(mut r) => {
+                r.numeros_sorteados = self.obter_dezenas_resultado(concurso)?;
+                r.faixas = self.obter_faixas_resultado(concurso)?;
+                Ok(Some(r))
+            }
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
             Err(e) => Err(e),
         }
     }
 
-    pub fn processar_acertos_concurso(
-        &self,
-        concurso: i32,
-        numeros_sorteados: &[i32],
-    ) -> Result<()> {
-        // Buscar todas as apostas ativas que incluem este concurso
-        let mut stmt = self.conn.prepare(
-            "SELECT id, numeros, concurso_inicial, quantidade_concursos
-             FROM apostas
-             WHERE ativa = 1
-             AND ?1 >= concurso_inicial
-             AND ?1 < (concurso_inicial + quantidade_concursos)",
+    /// Recalcula acertos e prêmios de todas as apostas ativas que incluem o
+    /// concurso. O resultado precisa ter sido salvo antes com `salvar_resultado`.
+    pub fn processar_acertos_concurso(&self, concurso: i32) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        Self::calcular_acertos(&tx, concurso, None)?;
+        tx.commit()
+    }
+
+    /// Grava acertos e prêmios do concurso para as apostas ativas que o
+    /// incluem (ou só para `aposta_id`), sem abrir transação própria
+    fn calcular_acertos(conn: &Connection, concurso: i32, aposta_id: Option<i64>) -> Result<()> {
+        // Contagem de acertos direto no SQL, cruzando as dezenas da aposta com as do sorteio
+        let mut stmt = conn.prepare(
+            "SELECT a.id, COUNT(ad.dezena), COUNT(rd.dezena)
+             FROM apostas a
+             JOIN aposta_dezenas ad ON ad.aposta_id = a.id
+             LEFT JOIN resultado_dezenas rd ON rd.concurso = ?1 AND rd.dezena = ad.dezena
+             WHERE a.excluida_em IS NULL AND a.invalida = 0
+             AND (?2 IS NULL OR a.id = ?2)
+             AND ?1 >= a.concurso_inicial
+             AND ?1 < (a.concurso_inicial + a.quantidade_concursos)
+             AND EXISTS (SELECT 1 FROM resultado_dezenas WHERE concurso = ?1)
+             GROUP BY a.id",
         )?;
 
         let apostas_afetadas = stmt
-            .query_map(params![concurso], |row| {
-                let id: i64 = row.get(0)?;
-                let numeros_str: String = row.get(1)?;
-                let numeros: Vec<i32> = serde_json::from_str(&numeros_str).unwrap_or_else(|_| {
-                    numeros_str
-                        .split(',')
-                        .filter_map(|s| s.trim().parse().ok())
-                        .collect()
-                });
-                Ok((id, numeros))
+            .query_map(params![concurso, aposta_id], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i32>(1)?,
+                    row.get::<_, i32>(2)?,
+                ))
             })?
-            .collect::<Result<Vec<(i64, Vec<i32>)>>>()?;
+            .collect::<Result<Vec<(i64, i32, i32)>>>()?;
+
+        let faixas = Self::faixas_resultado(conn, concurso)?;
 
-        for (id, numeros) in apostas_afetadas {
-            // Calcular acertos
-            let acertos = numeros
-                .iter()
-                .filter(|n| numeros_sorteados.contains(n))
-                .count() as i32;
+        for (id, quantidade_numeros, acertos) in apostas_afetadas {
+            // Expandir a aposta nas faixas premiadas
+            let contagem = domain::contagem_por_acertos(quantidade_numeros, acertos);
 
             // Inserir ou substituir na tabela de resultados de apostas
-            self.conn.execute(
-                "INSERT OR REPLACE INTO apostas_resultados (aposta_id, concurso, acertos)
-                 VALUES (?1, ?2, ?3)",
-                params![id, concurso, acertos],
+            conn.execute(
+                "INSERT OR REPLACE INTO apostas_resultados
+                 (aposta_id, concurso, acertos, senas, quinas, quadras)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    id,
+                    concurso,
+                    acertos,
+                    contagem.senas,
+                    contagem.quinas,
+                    contagem.quadras
+                ],
+            )?;
+
+            // Converter acertos em prêmios com o rateio do concurso
+            conn.execute(
+                "DELETE FROM apostas_premios WHERE aposta_id = ?1 AND concurso = ?2",
+                params![id, concurso],
             )?;
+            for premio in premios::calcular_premios(id, concurso, &contagem, &faixas) {
+                conn.execute(
+                    "INSERT INTO apostas_premios
+                     (aposta_id, concurso, faixa, acertos, quantidade, valor_unitario, valor_total)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    params![
+                        premio.aposta_id,
+                        premio.concurso,
+                        premio.faixa,
+                        premio.acertos,
+                        premio.quantidade,
+                        premio.valor_unitario,
+                        premio.valor_total
+                    ],
+                )?;
+            }
         }
 
         Ok(())
     }
 
-    pub fn obter_acertos_aposta(
-        &self,
-        aposta_id: i64,
-    ) -> Result<std::collections::HashMap<i32, i32>> {
+    pub fn obter_acertos_aposta(&self, aposta_id: i64) -> Result<HashMap<i32, i32>> {
         let mut stmt = self
             .conn
             .prepare("SELECT concurso, acertos FROM apostas_resultados WHERE aposta_id = ?1")?;
@@ -312,10 +1117,56 @@ impl Database {
             .query_map(params![aposta_id], |row| {
                 Ok((row.get::<_, i32>(0)?, row.get::<_, i32>(1)?))
             })?
-            .collect::<Result<std::collections::HashMap<i32, i32>>>()?;
+            .collect::<Result<HashMap<i32, i32>>>()?;
+
+        Ok(results)
+    }
+
+    pub fn obter_premiacoes_aposta(&self, aposta_id: i64) -> Result<HashMap<i32, ContagemFaixas>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT concurso, senas, quinas, quadras FROM apostas_resultados WHERE aposta_id = ?1",
+        )?;
+
+        let results = stmt
+            .query_map(params![aposta_id], |row| {
+                Ok((
+                    row.get::<_, i32>(0)?,
+                    ContagemFaixas {
+                        senas: row.get(1)?,
+                        quinas: row.get(2)?,
+                        quadras: row.get(3)?,
+                    },
+                ))
+            })?
+            .collect::<Result<HashMap<_, _>>>()?;
 
         Ok(results)
     }
+
+    pub fn obter_premios_aposta(&self, aposta_id: i64) -> Result<Vec<PremioAposta>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT aposta_id, concurso, faixa, acertos, quantidade, valor_unitario, valor_total
+             FROM apostas_premios
+             WHERE aposta_id = ?1
+             ORDER BY concurso, faixa",
+        )?;
+
+        let premios = stmt
+            .query_map(params![aposta_id], |row| {
+                Ok(PremioAposta {
+                    aposta_id: row.get(0)?,
+                    concurso: row.get(1)?,
+                    faixa: row.get(2)?,
+                    acertos: row.get(3)?,
+                    quantidade: row.get(4)?,
+                    valor_unitario: row.get(5)?,
+                    valor_total: row.get(6)?,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(premios)
+    }
 }
 
 #[cfg(test)]
@@ -324,17 +1175,45 @@ mod tests {
     use std::path::PathBuf;
 
     fn setup_test_db() -> Database {
-        let db = Database::new(PathBuf::from(":memory:")).unwrap();
+        let mut db = Database::new(PathBuf::from(":memory:")).unwrap();
         db.init().unwrap();
         db
     }
 
+    fn adicionar(
+        db: &Database,
+        numeros: Vec<i32>,
+        concurso: i32,
+        quantidade: i32,
+    ) -> Result<Aposta> {
+        db.adicionar_aposta(
+            &Dezenas::novas(numeros).unwrap(),
+            Concurso::novo(concurso).unwrap(),
+            QuantidadeConcursos::nova(quantidade).unwrap(),
+        )
+    }
+
+    fn atualizar(
+        db: &Database,
+        id: i64,
+        numeros: Vec<i32>,
+        concurso: i32,
+        quantidade: i32,
+    ) -> Result<Aposta> {
+        db.atualizar_aposta(
+            id,
+            &Dezenas::novas(numeros).unwrap(),
+            Concurso::novo(concurso).unwrap(),
+            QuantidadeConcursos::nova(quantidade).unwrap(),
+        )
+    }
+
     #[test]
     fn test_db_adicionar_listar_apostas() {
         let db = setup_test_db();
         let numeros = vec![1, 2, 3, 4, 5, 6];
 
-        let aposta = db.adicionar_aposta(numeros.clone(), 2650, 1).unwrap();
+        let aposta = adicionar(&db, numeros.clone(), 2650, 1).unwrap();
         assert_eq!(aposta.concurso_inicial, 2650);
         assert_eq!(aposta.numeros, numeros);
 
@@ -347,23 +1226,470 @@ mod tests {
     fn test_db_processar_acertos() {
         let db = setup_test_db();
         let aposta_numeros = vec![1, 2, 3, 4, 5, 6];
-        db.adicionar_aposta(aposta_numeros, 2650, 2).unwrap(); // Concursos 2650 e 2651
+        adicionar(&db, aposta_numeros, 2650, 2).unwrap(); // Concursos 2650 e 2651
 
-        let sorteio_2650 = vec![1, 2, 10, 11, 12, 13]; // 2 acertos
-        let res_2650 = crate::models::Resultado {
-            concurso: 2650,
-            numeros_sorteados: sorteio_2650.clone(),
-            data_sorteio: "2023-11-01".to_string(),
+        let res_2650 = Resultado {
             acumulado: false,
             valor_premio: Some(500000.0),
             ganhadores: Some(1),
             valor_total: Some(500000.0),
+            ..Resultado::para_teste(2650, vec![1, 2, 10, 11, 12, 13]) // 2 acertos
         };
         db.salvar_resultado(&res_2650).unwrap();
-        db.processar_acertos_concurso(2650, &sorteio_2650).unwrap();
+        db.processar_acertos_concurso(2650).unwrap();
 
         let acertos_map = db.obter_acertos_aposta(1).unwrap();
         assert_eq!(acertos_map.get(&2650), Some(&2));
         assert_eq!(acertos_map.get(&2651), None);
     }
+
+    #[test]
+    fn test_db_salvar_resultado_preserva_acertos() {
+        let db = setup_test_db();
+        let aposta = adicionar(&db, vec![6, 5, 4, 3, 2, 1], 2650, 1).unwrap();
+        // Dezenas sempre retornadas em ordem crescente
+        assert_eq!(aposta.numeros, vec![1, 2, 3, 4, 5, 6]);
+
+        let resultado = Resultado::para_teste(2650, vec![1, 2, 3, 40, 50, 60]);
+        db.salvar_resultado(&resultado).unwrap();
+        db.processar_acertos_concurso(2650).unwrap();
+
+        // Salvar de novo (ex.: atualização do rateio) não apaga os acertos já calculados
+        db.salvar_resultado(&resultado).unwrap();
+        assert_eq!(
+            db.obter_acertos_aposta(aposta.id).unwrap().get(&2650),
+            Some(&3)
+        );
+        assert_eq!(db.obter_dezenas_resultado(2650).unwrap().len(), 6);
+    }
+
+    #[test]
+    fn test_db_atualizar_aposta_recalcula_acertos() {
+        let db = setup_test_db();
+        let aposta = adicionar(&db, vec![1, 2, 3, 4, 5, 6], 2650, 1).unwrap();
+
+        for (concurso, numeros_sorteados) in [
+            (2650, vec![1, 2, 3, 4, 50, 60]),
+            (2651, vec![10, 20, 30, 40, 50, 60]),
+        ] {
+            db.salvar_resultado(&Resultado::para_teste(concurso, numeros_sorteados))
+                .unwrap();
+            db.processar_acertos_concurso(concurso).unwrap();
+        }
+        assert_eq!(
+            db.obter_acertos_aposta(aposta.id).unwrap().get(&2650),
+            Some(&4)
+        );
+
+        // Corrige as dezenas e estende para o concurso seguinte
+        let atualizada = atualizar(&db, aposta.id, vec![10, 20, 30, 40, 50, 60], 2650, 2).unwrap();
+        assert_eq!(atualizada.numeros, vec![10, 20, 30, 40, 50, 60]);
+        assert_eq!(atualizada.quantidade_concursos, 2);
+        assert_eq!(atualizada.acertos.get(&2650), Some(&2));
+        assert_eq!(atualizada.acertos.get(&2651), Some(&6));
+        assert_eq!(atualizada.premiacoes.get(&2651).unwrap().senas, 1);
+    }
+
+    #[test]
+    fn test_db_atualizar_aposta_inexistente() {
+        let db = setup_test_db();
+        assert!(atualizar(&db, 99, vec![1, 2, 3, 4, 5, 6], 2650, 1).is_err());
+    }
+
+    #[test]
+    fn test_db_arquivar_e_desarquivar() {
+        let db = setup_test_db();
+        let aposta = adicionar(&db, vec![1, 2, 3, 4, 5, 6], 2650, 1).unwrap();
+
+        db.arquivar_aposta(aposta.id).unwrap();
+        assert!(db.listar_apostas().unwrap().is_empty());
+        let arquivadas = db.listar_apostas_filtro(FiltroApostas::Arquivadas).unwrap();
+        assert_eq!(arquivadas.len(), 1);
+        assert!(!arquivadas[0].ativa);
+
+        db.desarquivar_aposta(aposta.id).unwrap();
+        assert_eq!(db.listar_apostas().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_db_lixeira_restaurar() {
+        let db = setup_test_db();
+        let aposta = adicionar(&db, vec![1, 2, 3, 4, 5, 6], 2650, 1).unwrap();
+
+        db.mover_para_lixeira(aposta.id).unwrap();
+        assert!(db.listar_apostas().unwrap().is_empty());
+        let lixeira = db.listar_apostas_filtro(FiltroApostas::Lixeira).unwrap();
+        assert_eq!(lixeira.len(), 1);
+        assert!(lixeira[0].excluida_em.is_some());
+
+        // Resultado que sai enquanto a aposta está na lixeira
+        db.salvar_resultado(&Resultado::para_teste(2650, vec![1, 2, 3, 4, 50, 60]))
+            .unwrap();
+        db.processar_acertos_concurso(2650).unwrap();
+        assert!(db.obter_acertos_aposta(aposta.id).unwrap().is_empty());
+
+        db.restaurar_aposta(aposta.id).unwrap();
+        let apostas = db.listar_apostas().unwrap();
+        assert_eq!(apostas.len(), 1);
+        assert_eq!(apostas[0].acertos.get(&2650), Some(&4));
+        assert!(db.restaurar_aposta(aposta.id).is_err());
+    }
+
+    #[test]
+    fn test_db_purgar_lixeira_respeita_retencao() {
+        let db = setup_test_db();
+        let antiga = adicionar(&db, vec![1, 2, 3, 4, 5, 6], 2650, 1).unwrap();
+        let recente = adicionar(&db, vec![1, 2, 3, 4, 5, 7], 2650, 1).unwrap();
+
+        db.mover_para_lixeira(antiga.id).unwrap();
+        db.mover_para_lixeira(recente.id).unwrap();
+        db.conn
+            .execute(
+                "UPDATE apostas SET excluida_em = datetime('now', '-40 days') WHERE id = ?1",
+                params![antiga.id],
+            )
+            .unwrap();
+
+        assert_eq!(
+            db.obter_retencao_lixeira().unwrap(),
+            RETENCAO_LIXEIRA_PADRAO
+        );
+        assert_eq!(db.purgar_lixeira(30).unwrap(), 1);
+
+        let lixeira = db.listar_apostas_filtro(FiltroApostas::Lixeira).unwrap();
+        assert_eq!(lixeira.len(), 1);
+        assert_eq!(lixeira[0].id, recente.id);
+        assert!(db.obter_dezenas_aposta(antiga.id).unwrap().is_empty());
+
+        db.definir_retencao_lixeira(7).unwrap();
+        assert_eq!(db.obter_retencao_lixeira().unwrap(), 7);
+    }
+
+    #[test]
+    fn test_db_preferencias_e_silencio_de_aposta() {
+        let db = setup_test_db();
+        assert_eq!(
+            db.obter_preferencias_notificacao().unwrap(),
+            PreferenciasNotificacao::default()
+        );
+        let preferencias = PreferenciasNotificacao {
+            faixa_minima: 1,
+            silencio_inicio: Some("22:00".to_string()),
+            silencio_fim: Some("07:00".to_string()),
+            notificar_sem_premio: true,
+            notificar_acumulado: true,
+        };
+        db.definir_preferencias_notificacao(&preferencias).unwrap();
+        assert_eq!(db.obter_preferencias_notificacao().unwrap(), preferencias);
+
+        let aposta = adicionar(&db, vec![1, 2, 3, 4, 5, 6], 2650, 1).unwrap();
+        assert!(!aposta.silenciada);
+        db.silenciar_aposta(aposta.id, true).unwrap();
+        assert!(db.listar_apostas().unwrap()[0].silenciada);
+        assert!(matches!(
+            db.silenciar_aposta(999, true),
+            Err(rusqlite::Error::QueryReturnedNoRows)
+        ));
+    }
+
+    #[test]
+    fn test_db_excluir_aposta_tudo_ou_nada() {
+        let db = setup_test_db();
+        let aposta = adicionar(&db, vec![1, 2, 3, 4, 5, 6], 2650, 1).unwrap();
+        // Falha no último DELETE: as dezenas não podem ter sido apagadas
+        db.conn
+            .execute_batch(
+                "CREATE TRIGGER falha_exclusao BEFORE DELETE ON apostas
+                 BEGIN SELECT RAISE(ABORT, 'falha simulada'); END;",
+            )
+            .unwrap();
+
+        assert!(db.excluir_aposta(aposta.id).is_err());
+        assert_eq!(
+            db.obter_dezenas_aposta(aposta.id).unwrap(),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+        assert_eq!(db.listar_apostas().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_db_banco_legado_com_aposta_invalida() {
+        let mut db = Database::new(":memory:".into()).unwrap();
+        db.conn
+            .execute_batch(
+                "CREATE TABLE apostas (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    numeros TEXT NOT NULL,
+                    concurso_inicial INTEGER NOT NULL,
+                    quantidade_concursos INTEGER NOT NULL,
+                    data_criacao DATETIME DEFAULT CURRENT_TIMESTAMP,
+                    ativa BOOLEAN DEFAULT 1
+                );
+                CREATE TABLE resultados (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    concurso INTEGER NOT NULL UNIQUE,
+                    numeros_sorteados TEXT NOT NULL,
+                    data_sorteio DATE,
+                    acumulado BOOLEAN,
+                    valor_premio REAL,
+                    data_verificacao DATETIME DEFAULT CURRENT_TIMESTAMP
+                );
+                INSERT INTO apostas (numeros, concurso_inicial, quantidade_concursos)
+                VALUES ('[1,2,3,4,5,6]', 2650, 1);
+                INSERT INTO apostas (numeros, concurso_inicial, quantidade_concursos)
+                VALUES ('[0,7,7,8,9,99]', 2650, 1);",
+            )
+            .unwrap();
+        db.init().unwrap();
+
+        let ativas = db.listar_apostas().unwrap();
+        assert_eq!(ativas.len(), 1);
+        assert_eq!(ativas[0].id, 1);
+        assert!(db
+            .listar_apostas_filtro(FiltroApostas::Arquivadas)
+            .unwrap()
+            .is_empty());
+
+        // Um resultado do período não confere a aposta inválida
+        db.salvar_resultado(&Resultado::para_teste(2650, vec![1, 2, 3, 7, 8, 9]))
+            .unwrap();
+        db.processar_acertos_concurso(2650).unwrap();
+        assert!(db.obter_acertos_aposta(2).unwrap().is_empty());
+        assert_eq!(db.obter_acertos_aposta(1).unwrap().get(&2650), Some(&3));
+    }
+
+    #[test]
+    fn test_db_listar_finalizadas() {
+        let db = setup_test_db();
+        let encerrada = adicionar(&db, vec![1, 2, 3, 4, 5, 6], 2650, 1).unwrap();
+        adicionar(&db, vec![1, 2, 3, 4, 5, 6], 2650, 3).unwrap();
+        db.salvar_resultado(&Resultado {
+            data_sorteio: "2023-11-04".to_string(),
+            ..Resultado::para_teste(2651, vec![1, 2, 3, 4, 50, 60])
+        })
+        .unwrap();
+
+        let finalizadas = db
+            .listar_apostas_filtro(FiltroApostas::Finalizadas)
+            .unwrap();
+        assert_eq!(finalizadas.len(), 1);
+        assert_eq!(finalizadas[0].id, encerrada.id);
+    }
+
+    #[test]
+    fn test_db_reconciliar_corrige_provisorio() {
+        let db = setup_test_db();
+        let aposta = adicionar(&db, vec![1, 2, 3, 4, 5, 6], 2700, 1).unwrap();
+
+        let mut provisorio = Resultado {
+            data_sorteio: "01/01/2024".to_string(),
+            fonte: Some("guidi".to_string()),
+            provisional: true,
+            ..Resultado::para_teste(2700, vec![1, 2, 3, 4, 50, 60])
+        };
+        db.salvar_resultado(&provisorio).unwrap();
+        db.processar_acertos_concurso(2700).unwrap();
+        assert_eq!(db.listar_resultados_provisorios().unwrap(), vec![2700]);
+        assert_eq!(
+            db.obter_acertos_aposta(aposta.id).unwrap().get(&2700),
+            Some(&4)
+        );
+
+        let salvo = db.obter_resultado(2700).unwrap().unwrap();
+        assert_eq!(salvo.fonte.as_deref(), Some("guidi"));
+        assert!(salvo.fetched_at.is_some());
+
+        // A fonte oficial traz uma dezena diferente
+        provisorio.numeros_sorteados = vec![1, 2, 3, 4, 5, 60];
+        provisorio.fonte = Some("caixa".to_string());
+        provisorio.provisional = false;
+        let diferencas = db.reconciliar_resultado(&provisorio).unwrap();
+
+        assert_eq!(diferencas.len(), 1);
+        assert!(diferencas[0].starts_with("dezenas: guidi"));
+        assert!(db.listar_resultados_provisorios().unwrap().is_empty());
+        assert_eq!(
+            db.obter_acertos_aposta(aposta.id).unwrap().get(&2700),
+            Some(&5)
+        );
+
+        // Conferir de novo não encontra diferenças
+        assert!(db.reconciliar_resultado(&provisorio).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_db_conflito_nao_grava_resultado() {
+        let db = setup_test_db();
+        let resultado = Resultado {
+            data_sorteio: "2024-01-01".to_string(),
+            acumulado: false,
+            ..Resultado::para_teste(2700, vec![1, 2, 3, 4, 5, 6])
+        };
+        let mut verificacao = VerificacaoResultado {
+            concurso: 2700,
+            status: StatusVerificacao::Conflito,
+            provedores: vec!["caixa".to_string(), "guidi".to_string()],
+            divergencias: vec!["dezenas diferentes".to_string()],
+            verificado_em: None,
+        };
+
+        assert!(!db
+            .salvar_resultado_verificado(&resultado, Some(&verificacao))
+            .unwrap());
+        assert!(db.obter_resultado(2700).unwrap().is_none());
+        assert_eq!(db.listar_verificacoes(true).unwrap().len(), 1);
+
+        // Nova verificação concordante substitui o conflito e grava
+        verificacao.status = StatusVerificacao::Concordante;
+        verificacao.divergencias.clear();
+        assert!(db
+            .salvar_resultado_verificado(&resultado, Some(&verificacao))
+            .unwrap());
+        assert!(db.obter_resultado(2700).unwrap().is_some());
+        assert!(db.listar_verificacoes(true).unwrap().is_empty());
+        let todas = db.listar_verificacoes(false).unwrap();
+        assert_eq!(todas[0].status, StatusVerificacao::Concordante);
+        assert_eq!(todas[0].provedores, vec!["caixa", "guidi"]);
+    }
+
+    #[test]
+    fn test_db_listar_apostas_em_lote() {
+        let db = setup_test_db();
+        let a1 = adicionar(&db, vec![1, 2, 3, 4, 5, 6], 2650, 2).unwrap();
+        let a2 = adicionar(&db, vec![10, 20, 30, 40, 50, 60, 7], 2651, 1).unwrap();
+
+        for (concurso, numeros_sorteados) in [
+            (2650, vec![1, 2, 3, 10, 20, 30]),
+            (2651, vec![4, 5, 6, 40, 50, 60]),
+        ] {
+            db.salvar_resultado(&Resultado::para_teste(concurso, numeros_sorteados))
+                .unwrap();
+            db.processar_acertos_concurso(concurso).unwrap();
+        }
+
+        let apostas = db.listar_apostas().unwrap();
+        assert_eq!(apostas.len(), 2);
+        // Mais recentes primeiro
+        assert_eq!(apostas[0].id, a2.id);
+        assert_eq!(apostas[0].numeros, vec![7, 10, 20, 30, 40, 50, 60]);
+        assert_eq!(apostas[0].acertos.len(), 1);
+        assert_eq!(apostas[0].acertos.get(&2651), Some(&3));
+
+        assert_eq!(apostas[1].id, a1.id);
+        assert_eq!(apostas[1].acertos.get(&2650), Some(&3));
+        assert_eq!(apostas[1].acertos.get(&2651), Some(&3));
+        assert_eq!(
+            apostas[1].resultados_concursos.get(&2651),
+            Some(&vec![4, 5, 6, 40, 50, 60])
+        );
+    }
+
+    #[test]
+    fn test_db_premiacoes_aposta_multipla() {
+        let db = setup_test_db();
+        let aposta = adicionar(&db, (1..=10).collect(), 2650, 1).unwrap();
+
+        let resultado = Resultado::para_teste(2650, vec![1, 2, 3, 4, 5, 60]);
+        db.salvar_resultado(&resultado).unwrap();
+        db.processar_acertos_concurso(2650).unwrap();
+
+        let apostas = db.listar_apostas().unwrap();
+        let listada = apostas.iter().find(|a| a.id == aposta.id).unwrap();
+        assert_eq!(listada.acertos.get(&2650), Some(&5));
+        assert_eq!(
+            listada.premiacoes.get(&2650),
+            Some(&ContagemFaixas {
+                senas: 0,
+                quinas: 5,
+                quadras: 50,
+            })
+        );
+    }
+
+    #[test]
+    fn test_db_salvar_resultado_com_faixas() {
+        let db = setup_test_db();
+        let faixas = vec![
+            FaixaPremio {
+                faixa: 1,
+                acertos: 6,
+                ganhadores: 0,
+                valor_premio: 0.0,
+            },
+            FaixaPremio {
+                faixa: 2,
+                acertos: 5,
+                ganhadores: 42,
+                valor_premio: 61234.5,
+            },
+            FaixaPremio {
+                faixa: 3,
+                acertos: 4,
+                ganhadores: 3100,
+                valor_premio: 980.12,
+            },
+        ];
+        let resultado = Resultado {
+            data_sorteio: "2024-03-02".to_string(),
+            valor_premio: Some(0.0),
+            ganhadores: Some(0),
+            valor_total: Some(45000000.0),
+            faixas: faixas.clone(),
+            ..Resultado::para_teste(2700, vec![5, 12, 23, 34, 45, 56])
+        };
+        db.salvar_resultado(&resultado).unwrap();
+
+        let salvo = db.obter_resultado(2700).unwrap().unwrap();
+        assert_eq!(salvo.faixas, faixas);
+
+        // Salvar novamente substitui as faixas em vez de duplicá-las
+        db.salvar_resultado(&resultado).unwrap();
+        assert_eq!(db.obter_faixas_resultado(2700).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_db_premios_aposta() {
+        let db = setup_test_db();
+        // 7 números, sorteio acerta 5 deles: 2 Quinas + 5 Quadras
+        let aposta = adicionar(&db, vec![1, 2, 3, 4, 5, 6, 7], 2700, 1).unwrap();
+        let resultado = Resultado {
+            data_sorteio: "2024-03-02".to_string(),
+            valor_premio: Some(0.0),
+            ganhadores: Some(0),
+            faixas: vec![
+                FaixaPremio {
+                    faixa: 1,
+                    acertos: 6,
+                    ganhadores: 0,
+                    valor_premio: 0.0,
+                },
+                FaixaPremio {
+                    faixa: 2,
+                    acertos: 5,
+                    ganhadores: 40,
+                    valor_premio: 50000.0,
+                },
+                FaixaPremio {
+                    faixa: 3,
+                    acertos: 4,
+                    ganhadores: 3000,
+                    valor_premio: 1000.0,
+                },
+            ],
+            ..Resultado::para_teste(2700, vec![1, 2, 3, 4, 5, 60])
+        };
+        db.salvar_resultado(&resultado).unwrap();
+        db.processar_acertos_concurso(2700).unwrap();
+
+        let premios = db.obter_premios_aposta(aposta.id).unwrap();
+        assert_eq!(premios.len(), 2);
+        assert_eq!(premios[0].quantidade, 2);
+        assert_eq!(premios[1].quantidade, 5);
+        let total: f64 = premios.iter().map(|p| p.valor_total).sum();
+        assert_eq!(total, 105000.0);
+
+        // Reprocessar não duplica os prêmios
+        db.processar_acertos_concurso(2700).unwrap();
+        assert_eq!(db.obter_premios_aposta(aposta.id).unwrap().len(), 2);
+    }
 }
@@ -0,0 +1,134 @@
+/*
+ * MegaSena Monitor - Minimalist desktop application for managing bets.
+ * Copyright (C) 2025 Zander Cattapreta
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Database actor: a dedicated thread owns the connection and runs queued jobs
+
+use crate::database::Database;
+use crate::error::MegaSenaError;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use tokio::sync::oneshot;
+
+type Tarefa = Box<dyn FnOnce(&mut Database) + Send>;
+
+/// Acesso ao banco por mensagens. Uma thread dedicada é dona do `Database`
+/// e executa as tarefas na ordem de chegada; quem pede só espera a resposta.
+/// Como não há lock a segurar, uma requisição de rede nunca bloqueia o banco:
+/// as tarefas devem conter apenas operações no banco.
+#[derive(Clone)]
+pub struct DbActor {
+    fila: mpsc::Sender<Tarefa>,
+}
+
+impl DbActor {
+    /// Move o banco para a thread do ator
+    pub fn iniciar(mut db: Database) -> Self {
+        let (fila, tarefas) = mpsc::channel::<Tarefa>();
+        std::thread::Builder::new()
+            .name("banco-de-dados".to_string())
+            .spawn(move || {
+                for tarefa in tarefas {
+                    // Uma tarefa que entra em pânico falha sozinha; o ator segue
+                    // atendendo as próximas
+                    if panic::catch_unwind(AssertUnwindSafe(|| tarefa(&mut db))).is_err() {
+                        eprintln!("Tarefa do banco de dados interrompida por pânico");
+                    }
+                }
+            })
+            .expect("falha ao iniciar a thread do banco de dados");
+        DbActor { fila }
+    }
+
+    fn enviar<T, F>(&self, tarefa: F) -> Result<oneshot::Receiver<T>, MegaSenaError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Database) -> T + Send + 'static,
+    {
+        let (resposta, recebida) = oneshot::channel();
+        self.fila
+            .send(Box::new(move |db: &mut Database| {
+                let _ = resposta.send(tarefa(db));
+            }))
+            .map_err(|_| encerrado())?;
+        Ok(recebida)
+    }
+
+    /// Executa `tarefa` na thread do banco e espera o resultado. Não deve ser
+    /// chamado de dentro de uma tarefa assíncrona; use `executar_async`.
+    pub fn executar<T, F>(&self, tarefa: F) -> Result<T, MegaSenaError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Database) -> T + Send + 'static,
+    {
+        self.enviar(tarefa)?
+            .blocking_recv()
+            .map_err(|_| interrompida())
+    }
+
+    /// Como `executar`, sem bloquear a thread do runtime enquanto espera
+    pub async fn executar_async<T, F>(&self, tarefa: F) -> Result<T, MegaSenaError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Database) -> T + Send + 'static,
+    {
+        self.enviar(tarefa)?.await.map_err(|_| interrompida())
+    }
+}
+
+fn encerrado() -> MegaSenaError {
+    MegaSenaError::BancoDeDados("a thread do banco de dados foi encerrada".to_string())
+}
+
+fn interrompida() -> MegaSenaError {
+    MegaSenaError::BancoDeDados("a operação foi interrompida por uma falha interna".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ator() -> DbActor {
+        let mut db = Database::new(":memory:".into()).unwrap();
+        db.init().unwrap();
+        DbActor::iniciar(db)
+    }
+
+    #[test]
+    fn test_executar_e_sobreviver_a_panico() {
+        let db = ator();
+        assert_eq!(
+            db.executar(|db| db.obter_retencao_lixeira()).unwrap(),
+            Ok(30)
+        );
+
+        let erro = db
+            .executar(|_| -> i32 { panic!("falha na tarefa") })
+            .unwrap_err();
+        assert_eq!(erro.code(), "database");
+
+        // O ator continua atendendo
+        assert!(db.executar(|db| db.listar_apostas()).unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_executar_async() {
+        let db = ator();
+        let apostas = db.executar_async(|db| db.listar_apostas()).await.unwrap();
+        assert!(apostas.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,403 @@
+/*
+ * MegaSena Monitor - Minimalist desktop application for managing bets.
+ * Copyright (C) 2025 Zander Cattapreta
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Domain rules for Mega-Sena bets
+
+use crate::error::MegaSenaError;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Deref;
+
+/// Menor e maior dezena do volante
+pub const DEZENA_MINIMA: i32 = 1;
+pub const DEZENA_MAXIMA: i32 = 60;
+
+/// Quantidade de dezenas aceita em uma aposta
+pub const MIN_DEZENAS: usize = 6;
+pub const MAX_DEZENAS: usize = 20;
+
+/// Máximo de concursos consecutivos cobertos por uma aposta (Teimosinha)
+pub const MAX_CONCURSOS: i32 = 12;
+
+/// Dezenas de uma aposta: de 6 a 20 números distintos entre 1 e 60, em
+/// ordem crescente. Só é construída por `Dezenas::novas`, então uma aposta
+/// com dezenas inválidas não chega ao banco.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<i32>", into = "Vec<i32>")]
+pub struct Dezenas(Vec<i32>);
+
+impl Dezenas {
+    /// Ordena os números e rejeita repetidos, fora do volante ou em
+    /// quantidade diferente de 6 a 20
+    pub fn novas(mut numeros: Vec<i32>) -> Result<Self, MegaSenaError> {
+        if numeros.len() < MIN_DEZENAS || numeros.len() > MAX_DEZENAS {
+            return Err(MegaSenaError::Validacao(format!(
+                "Selecione entre {} e {} números",
+                MIN_DEZENAS, MAX_DEZENAS
+            )));
+        }
+        if let Some(fora) = numeros
+            .iter()
+            .find(|n| !(DEZENA_MINIMA..=DEZENA_MAXIMA).contains(*n))
+        {
+            return Err(MegaSenaError::Validacao(format!(
+                "O número {} está fora do volante ({} a {})",
+                fora, DEZENA_MINIMA, DEZENA_MAXIMA
+            )));
+        }
+        numeros.sort_unstable();
+        if let Some(par) = numeros.windows(2).find(|par| par[0] == par[1]) {
+            return Err(MegaSenaError::Validacao(format!(
+                "O número {} foi escolhido mais de uma vez",
+                par[0]
+            )));
+        }
+        Ok(Dezenas(numeros))
+    }
+}
+
+impl Deref for Dezenas {
+    type Target = [i32];
+
+    fn deref(&self) -> &[i32] {
+        &self.0
+    }
+}
+
+impl TryFrom<Vec<i32>> for Dezenas {
+    type Error = MegaSenaError;
+
+    fn try_from(numeros: Vec<i32>) -> Result<Self, Self::Error> {
+        Dezenas::novas(numeros)
+    }
+}
+
+impl From<Dezenas> for Vec<i32> {
+    fn from(dezenas: Dezenas) -> Self {
+        dezenas.0
+    }
+}
+
+impl PartialEq<Vec<i32>> for Dezenas {
+    fn eq(&self, outro: &Vec<i32>) -> bool {
+        &self.0 == outro
+    }
+}
+
+/// Número de um concurso (sempre positivo)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "i32", into = "i32")]
+pub struct Concurso(i32);
+
+impl Concurso {
+    pub fn novo(numero: i32) -> Result<Self, MegaSenaError> {
+        if numero <= 0 {
+            return Err(MegaSenaError::Validacao(format!(
+                "Concurso inválido: {}",
+                numero
+            )));
+        }
+        Ok(Concurso(numero))
+    }
+
+    pub fn numero(self) -> i32 {
+        self.0
+    }
+}
+
+/// Quantos concursos consecutivos a aposta cobre, de 1 a 12
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "i32", into = "i32")]
+pub struct QuantidadeConcursos(i32);
+
+impl QuantidadeConcursos {
+    pub fn nova(quantidade: i32) -> Result<Self, MegaSenaError> {
+        if !(1..=MAX_CONCURSOS).contains(&quantidade) {
+            return Err(MegaSenaError::Validacao(format!(
+                "Quantidade de concursos deve ser entre 1 e {}",
+                MAX_CONCURSOS
+            )));
+        }
+        Ok(QuantidadeConcursos(quantidade))
+    }
+
+    pub fn valor(self) -> i32 {
+        self.0
+    }
+}
+
+/// Conversões comuns aos inteiros validados: serde, SQLite e comparação
+/// com `i32`. Ler do banco um valor inválido é erro de conversão.
+macro_rules! inteiro_validado {
+    ($tipo:ident, $construtor:ident) => {
+        impl TryFrom<i32> for $tipo {
+            type Error = MegaSenaError;
+
+            fn try_from(valor: i32) -> Result<Self, Self::Error> {
+                $tipo::$construtor(valor)
+            }
+        }
+
+        impl From<$tipo> for i32 {
+            fn from(valor: $tipo) -> Self {
+                valor.0
+            }
+        }
+
+        impl PartialEq<i32> for $tipo {
+            fn eq(&self, outro: &i32) -> bool {
+                self.0 == *outro
+            }
+        }
+
+        impl fmt::Display for $tipo {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl ToSql for $tipo {
+            fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+                self.0.to_sql()
+            }
+        }
+
+        impl FromSql for $tipo {
+            fn column_result(valor: ValueRef<'_>) -> FromSqlResult<Self> {
+                $tipo::$construtor(i32::column_result(valor)?)
+                    .map_err(|e| FromSqlError::Other(Box::new(e)))
+            }
+        }
+    };
+}
+
+inteiro_validado!(Concurso, novo);
+inteiro_validado!(QuantidadeConcursos, nova);
+
+/// Quantidade de apostas simples (6 números) premiadas em cada faixa.
+/// Uma aposta de 10 números que acerta 5 dezenas vale 5 Quinas e 50 Quadras.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContagemFaixas {
+    pub senas: i32,
+    pub quinas: i32,
+    pub quadras: i32,
+}
+
+impl ContagemFaixas {
+    /// Quantidade de apostas simples com exatamente `acertos` dezenas (4, 5 ou 6)
+    pub fn quantidade(&self, acertos: i32) -> i32 {
+        match acertos {
+            6 => self.senas,
+            5 => self.quinas,
+            4 => self.quadras,
+            _ => 0,
+        }
+    }
+
+    pub fn premiada(&self) -> bool {
+        self.senas > 0 || self.quinas > 0 || self.quadras > 0
+    }
+
+    /// Maior faixa premiada, na numeração da Caixa (1 = Sena, 2 = Quina, 3 = Quadra)
+    pub fn maior_faixa(&self) -> Option<i32> {
+        [(self.senas, 1), (self.quinas, 2), (self.quadras, 3)]
+            .iter()
+            .find(|(quantidade, _)| *quantidade > 0)
+            .map(|&(_, faixa)| faixa)
+    }
+
+    /// Descrição legível, da maior para a menor faixa (ex.: "1 Sena e 6 Quinas")
+    pub fn descricao(&self) -> String {
+        let partes: Vec<String> = [
+            (self.senas, "Sena", "Senas"),
+            (self.quinas, "Quina", "Quinas"),
+            (self.quadras, "Quadra", "Quadras"),
+        ]
+        .iter()
+        .filter(|(qtd, _, _)| *qtd > 0)
+        .map(|(qtd, singular, plural)| {
+            format!("{} {}", qtd, if *qtd == 1 { singular } else { plural })
+        })
+        .collect();
+
+        match partes.split_last() {
+            None => String::new(),
+            Some((ultima, [])) => ultima.clone(),
+            Some((ultima, resto)) => format!("{} e {}", resto.join(", "), ultima),
+        }
+    }
+}
+
+/// Coeficiente binomial C(n, k)
+pub fn combinacoes(n: i32, k: i32) -> i32 {
+    if k < 0 || n < 0 || k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    (0..k).fold(1i64, |acc, i| acc * (n - i) as i64 / (i + 1) as i64) as i32
+}
+
+/// Calcular acertos entre números apostados e sorteados
+pub fn contar_acertos(numeros_aposta: &[i32], numeros_sorteados: &[i32]) -> i32 {
+    numeros_aposta
+        .iter()
+        .filter(|n| numeros_sorteados.contains(n))
+        .count() as i32
+}
+
+/// Contagem por faixa para uma aposta de `quantidade_numeros` dezenas com
+/// `acertos` dezenas sorteadas
+pub fn contagem_por_acertos(quantidade_numeros: i32, acertos: i32) -> ContagemFaixas {
+    let na_faixa = |acertos_faixa: i32| {
+        combinacoes(acertos, acertos_faixa)
+            * combinacoes(quantidade_numeros - acertos, 6 - acertos_faixa)
+    };
+
+    ContagemFaixas {
+        senas: na_faixa(6),
+        quinas: na_faixa(5),
+        quadras: na_faixa(4),
+    }
+}
+
+/// Expande uma aposta de 6 a 20 números nas apostas simples que ela contém
+/// e conta quantas delas caem em cada faixa para o sorteio informado
+pub fn expandir_aposta(numeros_aposta: &[i32], numeros_sorteados: &[i32]) -> ContagemFaixas {
+    contagem_por_acertos(
+        numeros_aposta.len() as i32,
+        contar_acertos(numeros_aposta, numeros_sorteados),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calcular_acertos_sena() {
+        let aposta = vec![1, 2, 3, 4, 5, 6];
+        let sorteio = vec![1, 2, 3, 4, 5, 6];
+        assert_eq!(contar_acertos(&aposta, &sorteio), 6);
+    }
+
+    #[test]
+    fn test_calcular_acertos_quadra() {
+        let aposta = vec![1, 2, 3, 4, 5, 6];
+        let sorteio = vec![1, 2, 3, 4, 10, 11];
+        assert_eq!(contar_acertos(&aposta, &sorteio), 4);
+    }
+
+    #[test]
+    fn test_calcular_acertos_zero() {
+        let aposta = vec![1, 2, 3, 4, 5, 6];
+        let sorteio = vec![10, 11, 12, 13, 14, 15];
+        assert_eq!(contar_acertos(&aposta, &sorteio), 0);
+    }
+
+    #[test]
+    fn test_combinacoes() {
+        assert_eq!(combinacoes(6, 6), 1);
+        assert_eq!(combinacoes(10, 2), 45);
+        assert_eq!(combinacoes(20, 6), 38760);
+        assert_eq!(combinacoes(3, 4), 0);
+    }
+
+    #[test]
+    fn test_expandir_aposta_simples() {
+        let contagem = expandir_aposta(&[1, 2, 3, 4, 5, 6], &[1, 2, 3, 4, 5, 60]);
+        assert_eq!(
+            contagem,
+            ContagemFaixas {
+                senas: 0,
+                quinas: 1,
+                quadras: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_expandir_aposta_dez_numeros_quina() {
+        let aposta: Vec<i32> = (1..=10).collect();
+        let contagem = expandir_aposta(&aposta, &[1, 2, 3, 4, 5, 60]);
+        assert_eq!(contagem.senas, 0);
+        assert_eq!(contagem.quinas, 5);
+        assert_eq!(contagem.quadras, 50);
+    }
+
+    #[test]
+    fn test_expandir_aposta_vinte_numeros_sena() {
+        let aposta: Vec<i32> = (1..=20).collect();
+        let contagem = expandir_aposta(&aposta, &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(contagem.senas, 1);
+        assert_eq!(contagem.quinas, 6 * 14);
+        assert_eq!(contagem.quadras, 15 * 91);
+    }
+
+    #[test]
+    fn test_dezenas_validas_ficam_ordenadas() {
+        let dezenas = Dezenas::novas(vec![60, 5, 33, 1, 12, 48]).unwrap();
+        assert_eq!(dezenas, vec![1, 5, 12, 33, 48, 60]);
+        assert!(Dezenas::novas((1..=20).collect()).is_ok());
+    }
+
+    #[test]
+    fn test_dezenas_invalidas() {
+        for numeros in [
+            vec![1, 2, 3, 4, 5],
+            (1..=21).collect(),
+            vec![0, 2, 3, 4, 5, 6],
+            vec![1, 2, 3, 4, 5, 61],
+            vec![1, 2, 3, 4, 5, 5],
+        ] {
+            let erro = Dezenas::novas(numeros.clone()).unwrap_err();
+            assert_eq!(erro.code(), "validation", "{:?}", numeros);
+        }
+    }
+
+    #[test]
+    fn test_dezenas_desserializacao_valida() {
+        let dezenas: Dezenas = serde_json::from_str("[6, 5, 4, 3, 2, 1]").unwrap();
+        assert_eq!(dezenas, vec![1, 2, 3, 4, 5, 6]);
+        assert!(serde_json::from_str::<Dezenas>("[1, 1, 2, 3, 4, 5]").is_err());
+        assert_eq!(serde_json::to_string(&dezenas).unwrap(), "[1,2,3,4,5,6]");
+    }
+
+    #[test]
+    fn test_concurso_e_quantidade() {
+        assert_eq!(Concurso::novo(2954).unwrap(), 2954);
+        assert!(Concurso::novo(0).is_err());
+        assert_eq!(QuantidadeConcursos::nova(12).unwrap(), 12);
+        assert!(QuantidadeConcursos::nova(0).is_err());
+        assert!(QuantidadeConcursos::nova(13).is_err());
+    }
+
+    #[test]
+    fn test_descricao_contagem() {
+        let contagem = ContagemFaixas {
+            senas: 1,
+            quinas: 6,
+            quadras: 0,
+        };
+        assert_eq!(contagem.descricao(), "1 Sena e 6 Quinas");
+        assert!(!ContagemFaixas::default().premiada());
+        assert_eq!(contagem.maior_faixa(), Some(1));
+        assert_eq!(ContagemFaixas::default().maior_faixa(), None);
+    }
+}
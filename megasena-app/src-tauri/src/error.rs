@@ -0,0 +1,197 @@
+/*
+ * MegaSena Monitor - Minimalist desktop application for managing bets.
+ * Copyright (C) 2025 Zander Cattapreta
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Application error type, serialized to the frontend as {code, message, details}
+
+use crate::migrations::MigrationError;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use serde_json::{json, Value};
+use std::fmt;
+use std::sync::PoisonError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MegaSenaError {
+    /// Sem conexão, timeout ou provedor indisponível
+    Rede(String),
+    /// Provedor respondeu com um status HTTP de erro
+    StatusHttp {
+        provedor: String,
+        status: u16,
+    },
+    /// Resposta ou arquivo fora do formato esperado
+    Formato(String),
+    /// Nenhum provedor publicou o concurso ainda
+    NaoSorteado {
+        concurso: i32,
+    },
+    /// Dados informados pelo usuário são inválidos
+    Validacao(String),
+    /// Provedores divergem sobre o concurso; o resultado não foi gravado
+    Conflito {
+        concurso: i32,
+        divergencias: Vec<String>,
+    },
+    /// Registro inexistente (ex.: aposta apagada)
+    NaoEncontrado(String),
+    /// Falha ao ler um arquivo local
+    Arquivo(String),
+    BancoDeDados(String),
+    /// Outra thread entrou em pânico segurando o lock do banco
+    LockEnvenenado,
+}
+
+impl MegaSenaError {
+    /// Identificador estável usado pelo frontend para decidir o que mostrar
+    pub fn code(&self) -> &'static str {
+        match self {
+            MegaSenaError::Rede(_) => "network",
+            MegaSenaError::StatusHttp { .. } => "providerHttp",
+            MegaSenaError::Formato(_) => "parse",
+            MegaSenaError::NaoSorteado { .. } => "notDrawn",
+            MegaSenaError::Validacao(_) => "validation",
+            MegaSenaError::Conflito { .. } => "conflict",
+            MegaSenaError::NaoEncontrado(_) => "notFound",
+            MegaSenaError::Arquivo(_) => "file",
+            MegaSenaError::BancoDeDados(_) => "database",
+            MegaSenaError::LockEnvenenado => "lockPoisoned",
+        }
+    }
+
+    /// Dados estruturados do erro, quando houver
+    pub fn details(&self) -> Option<Value> {
+        match self {
+            MegaSenaError::StatusHttp { provedor, status } => {
+                Some(json!({ "provedor": provedor, "status": status }))
+            }
+            MegaSenaError::NaoSorteado { concurso } => Some(json!({ "concurso": concurso })),
+            MegaSenaError::Conflito {
+                concurso,
+                divergencias,
+            } => Some(json!({ "concurso": concurso, "divergencias": divergencias })),
+            MegaSenaError::Rede(causa)
+            | MegaSenaError::Formato(causa)
+            | MegaSenaError::Arquivo(causa)
+            | MegaSenaError::BancoDeDados(causa) => Some(json!({ "causa": causa })),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for MegaSenaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MegaSenaError::Rede(causa) => write!(
+                f,
+                "Não foi possível acessar os provedores de resultados ({}). Verifique sua conexão e tente novamente em alguns instantes.",
+                causa
+            ),
+            MegaSenaError::StatusHttp { provedor, status } => {
+                write!(f, "O provedor '{}' respondeu com erro {}", provedor, status)
+            }
+            MegaSenaError::Formato(causa) => write!(f, "Resposta em formato inesperado: {}", causa),
+            MegaSenaError::NaoSorteado { concurso } => write!(
+                f,
+                "O concurso {} ainda não foi sorteado ou não foi publicado pelos sistemas oficiais",
+                concurso
+            ),
+            MegaSenaError::Validacao(msg) | MegaSenaError::NaoEncontrado(msg) => {
+                write!(f, "{}", msg)
+            }
+            MegaSenaError::Conflito { concurso, .. } => write!(
+                f,
+                "Os provedores divergem sobre o concurso {}; o resultado não foi salvo",
+                concurso
+            ),
+            MegaSenaError::Arquivo(causa) => write!(f, "Erro ao ler o arquivo: {}", causa),
+            MegaSenaError::BancoDeDados(causa) => write!(f, "Erro no banco de dados: {}", causa),
+            MegaSenaError::LockEnvenenado => write!(
+                f,
+                "O banco de dados ficou indisponível após uma falha interna. Reinicie o aplicativo."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MegaSenaError {}
+
+/// Enviado ao frontend como `{ code, message, details }`
+impl Serialize for MegaSenaError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut estado = serializer.serialize_struct("MegaSenaError", 3)?;
+        estado.serialize_field("code", self.code())?;
+        estado.serialize_field("message", &self.to_string())?;
+        estado.serialize_field("details", &self.details())?;
+        estado.end()
+    }
+}
+
+impl From<rusqlite::Error> for MegaSenaError {
+    fn from(e: rusqlite::Error) -> Self {
+        match e {
+            rusqlite::Error::QueryReturnedNoRows => {
+                MegaSenaError::NaoEncontrado("Registro não encontrado".to_string())
+            }
+            e => MegaSenaError::BancoDeDados(e.to_string()),
+        }
+    }
+}
+
+impl From<MigrationError> for MegaSenaError {
+    fn from(e: MigrationError) -> Self {
+        MegaSenaError::BancoDeDados(e.to_string())
+    }
+}
+
+impl<T> From<PoisonError<T>> for MegaSenaError {
+    fn from(_: PoisonError<T>) -> Self {
+        MegaSenaError::LockEnvenenado
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializa_code_message_details() {
+        let erro = MegaSenaError::NaoSorteado { concurso: 2955 };
+        let valor = serde_json::to_value(&erro).unwrap();
+        assert_eq!(valor["code"], "notDrawn");
+        assert_eq!(valor["details"]["concurso"], 2955);
+        assert!(valor["message"].as_str().unwrap().contains("2955"));
+
+        let valor = serde_json::to_value(MegaSenaError::LockEnvenenado).unwrap();
+        assert_eq!(valor["code"], "lockPoisoned");
+        assert!(valor["details"].is_null());
+    }
+
+    #[test]
+    fn test_conversoes() {
+        let erro: MegaSenaError = rusqlite::Error::InvalidQuery.into();
+        assert_eq!(erro.code(), "database");
+
+        let mutex = std::sync::Mutex::new(());
+        let _ = std::panic::catch_unwind(|| {
+            let _guard = mutex.lock().unwrap();
+            panic!("envenena o lock");
+        });
+        let erro: MegaSenaError = mutex.lock().unwrap_err().into();
+        assert_eq!(erro, MegaSenaError::LockEnvenenado);
+    }
+}
@@ -0,0 +1,106 @@
+/*
+ * MegaSena Monitor - Minimalist desktop application for managing bets.
+ * Copyright (C) 2025 Zander Cattapreta
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Typed events sent to every frontend window
+
+use crate::models::Resultado;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// Payload de `sync-started`
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InicioSincronizacao {
+    /// Concursos a buscar
+    pub total: usize,
+}
+
+/// Payload de `sync-progress`, enviado a cada concurso concluído
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressoSincronizacao {
+    pub done: usize,
+    pub total: usize,
+    pub concurso: i32,
+}
+
+/// Payload de `aposta-premiada`
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApostaPremiada {
+    pub aposta_id: i64,
+    pub concurso: i32,
+    /// Maior faixa atingida (1 = Sena, 2 = Quina, 3 = Quadra)
+    pub faixa: i32,
+}
+
+/// Payload de `aposta-alterada`
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApostaAlterada {
+    pub aposta_id: i64,
+}
+
+/// Eventos de domínio. O payload de cada variante é o valor que ela carrega.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Evento {
+    SincronizacaoIniciada(InicioSincronizacao),
+    ProgressoSincronizacao(ProgressoSincronizacao),
+    ResultadoSalvo(Resultado),
+    ApostaPremiada(ApostaPremiada),
+    ApostaAlterada(ApostaAlterada),
+}
+
+impl Evento {
+    pub fn nome(&self) -> &'static str {
+        match self {
+            Evento::SincronizacaoIniciada(_) => "sync-started",
+            Evento::ProgressoSincronizacao(_) => "sync-progress",
+            Evento::ResultadoSalvo(_) => "resultado-salvo",
+            Evento::ApostaPremiada(_) => "aposta-premiada",
+            Evento::ApostaAlterada(_) => "aposta-alterada",
+        }
+    }
+
+    /// Envia o evento a todas as janelas
+    pub fn emitir(&self, app: &AppHandle) {
+        if let Err(e) = app.emit(self.nome(), self) {
+            eprintln!("Falha ao emitir '{}': {:?}", self.nome(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_sem_envelope() {
+        let evento = Evento::ApostaPremiada(ApostaPremiada {
+            aposta_id: 7,
+            concurso: 2954,
+            faixa: 2,
+        });
+        assert_eq!(evento.nome(), "aposta-premiada");
+        assert_eq!(
+            serde_json::to_value(&evento).unwrap(),
+            serde_json::json!({ "apostaId": 7, "concurso": 2954, "faixa": 2 })
+        );
+    }
+}
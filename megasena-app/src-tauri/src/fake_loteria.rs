@@ -0,0 +1,379 @@
+/*
+ * MegaSena Monitor - Minimalist desktop application for managing bets.
+ * Copyright (C) 2025 Zander Cattapreta
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Fake lottery HTTP server serving Caixa-shaped fixtures, for offline development and tests
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Tipo de falha simulada
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModoFalha {
+    /// Responde 500 Internal Server Error
+    Erro500,
+    /// Segura a conexão por `atraso_timeout` antes de responder
+    Timeout,
+    /// Responde 200 com um corpo que não é JSON válido
+    JsonInvalido,
+}
+
+impl ModoFalha {
+    pub fn from_nome(nome: &str) -> Option<ModoFalha> {
+        match nome {
+            "500" => Some(ModoFalha::Erro500),
+            "timeout" => Some(ModoFalha::Timeout),
+            "json" => Some(ModoFalha::JsonInvalido),
+            _ => None,
+        }
+    }
+}
+
+/// Injeção de falhas: a cada `a_cada` requisições (1 = todas), até
+/// `limite` falhas no total (`None` = sem limite)
+#[derive(Debug, Clone)]
+pub struct InjecaoFalhas {
+    pub modo: ModoFalha,
+    pub a_cada: u32,
+    pub limite: Option<u32>,
+    pub atraso_timeout: Duration,
+}
+
+impl InjecaoFalhas {
+    pub fn new(modo: ModoFalha) -> Self {
+        InjecaoFalhas {
+            modo,
+            a_cada: 1,
+            limite: None,
+            atraso_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+struct Estado {
+    /// JSON de cada concurso, no formato da API da Caixa
+    fixtures: HashMap<i32, String>,
+    injecao: Option<InjecaoFalhas>,
+    requisicoes: AtomicU32,
+    falhas: AtomicU32,
+    parar: AtomicBool,
+}
+
+impl Estado {
+    /// Falha a aplicar na `n`-ésima requisição, se houver
+    fn falha_da_vez(&self, n: u32) -> Option<&InjecaoFalhas> {
+        let injecao = self.injecao.as_ref()?;
+        if !n.is_multiple_of(injecao.a_cada.max(1)) {
+            return None;
+        }
+        if let Some(limite) = injecao.limite {
+            if self.falhas.fetch_add(1, Ordering::SeqCst) >= limite {
+                return None;
+            }
+        }
+        Some(injecao)
+    }
+}
+
+/// Servidor em execução; para quando sai de escopo
+pub struct FakeLoteriaServer {
+    endereco: SocketAddr,
+    estado: Arc<Estado>,
+}
+
+impl FakeLoteriaServer {
+    /// Sobe o servidor em `endereco` (use porta 0 para uma porta livre)
+    pub fn iniciar(
+        endereco: &str,
+        fixtures: HashMap<i32, String>,
+        injecao: Option<InjecaoFalhas>,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind(endereco)?;
+        let endereco = listener.local_addr()?;
+        let estado = Arc::new(Estado {
+            fixtures,
+            injecao,
+            requisicoes: AtomicU32::new(0),
+            falhas: AtomicU32::new(0),
+            parar: AtomicBool::new(false),
+        });
+
+        let estado_servidor = estado.clone();
+        thread::spawn(move || {
+            for conexao in listener.incoming() {
+                if estado_servidor.parar.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(conexao) = conexao else { continue };
+                let estado = estado_servidor.clone();
+                thread::spawn(move || {
+                    if let Err(e) = atender(conexao, &estado) {
+                        eprintln!("fake_loteria: erro na conexão: {}", e);
+                    }
+                });
+            }
+        });
+
+        Ok(FakeLoteriaServer { endereco, estado })
+    }
+
+    pub fn endereco(&self) -> SocketAddr {
+        self.endereco
+    }
+
+    /// URL base para `baseUrl` na configuração; qualquer prefixo de caminho
+    /// funciona, então o mesmo servidor atende Caixa e Guidi
+    pub fn url_base(&self, prefixo: &str) -> String {
+        format!("http://{}/{}", self.endereco, prefixo.trim_matches('/'))
+    }
+
+    /// Total de requisições recebidas
+    pub fn requisicoes(&self) -> u32 {
+        self.estado.requisicoes.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for FakeLoteriaServer {
+    fn drop(&mut self) {
+        self.estado.parar.store(true, Ordering::SeqCst);
+        // Desbloqueia o accept para a thread perceber o pedido de parada
+        let _ = TcpStream::connect(self.endereco);
+    }
+}
+
+/// Lê `<concurso>.json` de um diretório
+pub fn carregar_fixtures(diretorio: &Path) -> io::Result<HashMap<i32, String>> {
+    let mut fixtures = HashMap::new();
+    for entrada in std::fs::read_dir(diretorio)? {
+        let caminho = entrada?.path();
+        if caminho.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let concurso = caminho
+            .file_stem()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.parse::<i32>().ok());
+        if let Some(concurso) = concurso {
+            fixtures.insert(concurso, std::fs::read_to_string(&caminho)?);
+        }
+    }
+    Ok(fixtures)
+}
+
+fn atender(conexao: TcpStream, estado: &Estado) -> io::Result<()> {
+    let mut leitor = BufReader::new(conexao.try_clone()?);
+    let mut linha_requisicao = String::new();
+    leitor.read_line(&mut linha_requisicao)?;
+
+    // Descartar cabeçalhos
+    let mut cabecalho = String::new();
+    while leitor.read_line(&mut cabecalho)? > 2 {
+        cabecalho.clear();
+    }
+
+    let caminho = linha_requisicao
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or("/")
+        .split('?')
+        .next()
+        .unwrap_or("/");
+
+    if estado.parar.load(Ordering::SeqCst) {
+        return Ok(());
+    }
+
+    let n = estado.requisicoes.fetch_add(1, Ordering::SeqCst) + 1;
+    let (status, corpo) = match estado.falha_da_vez(n) {
+        Some(injecao) => match injecao.modo {
+            ModoFalha::Erro500 => (500, r#"{"erro":"falha simulada"}"#.to_string()),
+            ModoFalha::Timeout => {
+                thread::sleep(injecao.atraso_timeout);
+                responder_fixture(caminho, &estado.fixtures)
+            }
+            ModoFalha::JsonInvalido => (200, r#"{"numero": 29"#.to_string()),
+        },
+        None => responder_fixture(caminho, &estado.fixtures),
+    };
+
+    println!("fake_loteria: {} -> {}", caminho, status);
+    escrever_resposta(conexao, status, &corpo)
+}
+
+/// `/<prefixo>/<n>` devolve o concurso n; `/<prefixo>/` e `/<prefixo>/ultimo`
+/// devolvem o mais recente
+fn responder_fixture(caminho: &str, fixtures: &HashMap<i32, String>) -> (u16, String) {
+    let segmento = caminho
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or("");
+    let concurso = match segmento.parse::<i32>() {
+        Ok(n) => Some(n),
+        Err(_) if caminho.ends_with('/') || segmento == "ultimo" => fixtures.keys().max().copied(),
+        Err(_) => None,
+    };
+
+    match concurso.and_then(|n| fixtures.get(&n)) {
+        Some(json) => (200, json.clone()),
+        None => (404, r#"{"erro":"concurso não encontrado"}"#.to_string()),
+    }
+}
+
+fn escrever_resposta(mut conexao: TcpStream, status: u16, corpo: &str) -> io::Result<()> {
+    let motivo = match status {
+        200 => "OK",
+        404 => "Not Found",
+        _ => "Internal Server Error",
+    };
+    write!(
+        conexao,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        motivo,
+        corpo.len(),
+        corpo
+    )?;
+    conexao.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{CaixaProvider, ErroBusca, GuidiProvider, ProviderChain, ResultProvider};
+    use crate::database::Database;
+    use crate::domain::{Concurso, Dezenas, QuantidadeConcursos};
+
+    const FIXTURE_2954: &str = include_str!("../fixtures/megasena/2954.json");
+
+    fn fixtures() -> HashMap<i32, String> {
+        let mut fixtures = HashMap::new();
+        fixtures.insert(2954, FIXTURE_2954.to_string());
+        fixtures
+    }
+
+    fn caixa(servidor: &FakeLoteriaServer, timeout_secs: u64) -> CaixaProvider {
+        CaixaProvider::new(0, timeout_secs)
+            .unwrap()
+            .with_base_url(&servidor.url_base("caixa"))
+    }
+
+    #[test]
+    fn test_serve_fixture_e_ultimo() {
+        let servidor = FakeLoteriaServer::iniciar("127.0.0.1:0", fixtures(), None).unwrap();
+
+        let resultado = caixa(&servidor, 5).fetch(2954).unwrap();
+        assert_eq!(resultado.numeros_sorteados, vec![1, 9, 37, 39, 42, 44]);
+        assert_eq!(caixa(&servidor, 5).latest().unwrap().concurso, 2954);
+
+        let guidi = GuidiProvider::new(0, 5)
+            .unwrap()
+            .with_base_url(&servidor.url_base("guidi"));
+        assert_eq!(guidi.latest().unwrap().concurso, 2954);
+        assert_eq!(guidi.fetch(2955), Err(ErroBusca::NaoEncontrado));
+    }
+
+    #[test]
+    fn test_injecao_erro_500_e_json_invalido() {
+        let servidor = FakeLoteriaServer::iniciar(
+            "127.0.0.1:0",
+            fixtures(),
+            Some(InjecaoFalhas::new(ModoFalha::Erro500)),
+        )
+        .unwrap();
+        let erro = caixa(&servidor, 5).fetch(2954).unwrap_err();
+        assert_eq!(erro, ErroBusca::Http(500));
+        assert!(erro.transitorio());
+
+        let servidor = FakeLoteriaServer::iniciar(
+            "127.0.0.1:0",
+            fixtures(),
+            Some(InjecaoFalhas::new(ModoFalha::JsonInvalido)),
+        )
+        .unwrap();
+        assert!(matches!(
+            caixa(&servidor, 5).fetch(2954),
+            Err(ErroBusca::Invalido(_))
+        ));
+    }
+
+    #[test]
+    fn test_injecao_timeout() {
+        let mut injecao = InjecaoFalhas::new(ModoFalha::Timeout);
+        injecao.atraso_timeout = Duration::from_secs(3);
+        let servidor =
+            FakeLoteriaServer::iniciar("127.0.0.1:0", fixtures(), Some(injecao)).unwrap();
+
+        assert!(matches!(
+            caixa(&servidor, 1).fetch(2954),
+            Err(ErroBusca::Transitorio(_))
+        ));
+    }
+
+    #[test]
+    fn test_pipeline_completo_com_falhas_iniciais() {
+        // Duas respostas 500 antes de o servidor se recuperar: o retry da
+        // cadeia absorve as falhas e o resultado chega ao banco
+        let mut injecao = InjecaoFalhas::new(ModoFalha::Erro500);
+        injecao.limite = Some(2);
+        let servidor =
+            FakeLoteriaServer::iniciar("127.0.0.1:0", fixtures(), Some(injecao)).unwrap();
+
+        let chain = ProviderChain::new(vec![Box::new(caixa(&servidor, 5))]).with_retry(
+            crate::config::RetryConfig {
+                max_attempts: 3,
+                base_delay_ms: 1,
+                max_delay_ms: 5,
+            },
+        );
+
+        let mut db = Database::new(":memory:".into()).unwrap();
+        db.init().unwrap();
+        let aposta = db
+            .adicionar_aposta(
+                &Dezenas::novas(vec![1, 9, 37, 39, 50, 60]).unwrap(),
+                Concurso::novo(2954).unwrap(),
+                QuantidadeConcursos::nova(1).unwrap(),
+            )
+            .unwrap();
+
+        let resultado = chain.verificar_resultado(2954).unwrap();
+        db.salvar_resultado(&resultado).unwrap();
+        db.processar_acertos_concurso(2954).unwrap();
+
+        assert_eq!(servidor.requisicoes(), 3);
+        assert_eq!(
+            db.obter_acertos_aposta(aposta.id).unwrap().get(&2954),
+            Some(&4)
+        );
+        assert_eq!(
+            db.obter_premios_aposta(aposta.id).unwrap()[0].valor_total,
+            419.51
+        );
+        // O corpo recebido fica guardado tal como veio
+        assert_eq!(
+            db.obter_payload(2954, "caixa").unwrap().as_ref(),
+            fixtures().get(&2954)
+        );
+    }
+}
@@ -0,0 +1,767 @@
+/*
+ * MegaSena Monitor - Minimalist desktop application for managing bets.
+ * Copyright (C) 2025 Zander Cattapreta
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Historical results import from the Caixa download (XLSX/HTML) or CSV
+
+use crate::api;
+use crate::db_actor::DbActor;
+use crate::error::MegaSenaError;
+use crate::models::{FaixaPremio, RespostaBruta, Resultado, ResumoImportacao};
+use calamine::{open_workbook_auto, Data, Reader};
+use chrono::{Duration, NaiveDate};
+use std::path::{Path, PathBuf};
+
+/// Valor de `fonte` para resultados importados
+pub const FONTE_PLANILHA: &str = "planilha";
+
+/// Resultados lidos de uma planilha
+#[derive(Debug, Default)]
+pub struct Planilha {
+    pub resultados: Vec<Resultado>,
+    /// Linhas com número de concurso que não formaram um resultado válido
+    pub linhas_ignoradas: usize,
+}
+
+/// Posição das colunas relevantes no cabeçalho da planilha
+#[derive(Debug)]
+struct Colunas {
+    concurso: usize,
+    data: usize,
+    bolas: [usize; 6],
+    /// Ganhadores e rateio por faixa, da Sena (índice 0) à Quadra (índice 2)
+    ganhadores: [Option<usize>; 3],
+    rateio: [Option<usize>; 3],
+    valor_acumulado: Option<usize>,
+    estimativa: Option<usize>,
+}
+
+/// Lê o arquivo de resultados pelo formato indicado na extensão:
+/// `.xlsx`/`.xls`/`.ods` (download atual da Caixa), `.htm`/`.html`
+/// (download antigo) ou `.csv` (separado por `;` ou `,`).
+pub fn ler_arquivo(caminho: &Path) -> Result<Planilha, MegaSenaError> {
+    let extensao = caminho
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+
+    let linhas = match extensao.as_str() {
+        "xlsx" | "xlsm" | "xls" | "ods" => ler_xlsx(caminho).map_err(MegaSenaError::Arquivo)?,
+        "htm" | "html" => ler_html(&ler_texto(caminho)?),
+        "csv" | "txt" => ler_csv(&ler_texto(caminho)?).map_err(MegaSenaError::Formato)?,
+        _ => {
+            return Err(MegaSenaError::Validacao(format!(
+                "Formato de arquivo não suportado: {:?}",
+                caminho
+            )))
+        }
+    };
+
+    converter_linhas(&linhas).map_err(MegaSenaError::Formato)
+}
+
+/// Concursos gravados por tarefa do banco. Entre um lote e outro o ator
+/// atende os demais comandos e a verificação em segundo plano.
+const TAMANHO_LOTE: usize = 200;
+
+/// Importa o arquivo para o banco. Pode ser repetido sem efeito colateral:
+/// concursos já armazenados com os mesmos dados são contados como inalterados.
+/// A leitura da planilha acontece fora da thread do banco e a gravação é
+/// feita em lotes; `progresso` recebe os concursos processados ao fim de cada
+/// lote.
+pub async fn importar_arquivo(
+    db: &DbActor,
+    caminho: PathBuf,
+    progresso: &(dyn Fn(usize, usize) + Sync),
+) -> Result<ResumoImportacao, MegaSenaError> {
+    let planilha = em_segundo_plano(move || {
+        let planilha = ler_arquivo(&caminho)?;
+        println!(
+            "Importando {} concursos de {:?} ({} linhas ignoradas)",
+            planilha.resultados.len(),
+            caminho,
+            planilha.linhas_ignoradas
+        );
+        Ok(planilha)
+    })
+    .await?;
+
+    gravar_em_lotes(
+        db,
+        planilha.resultados,
+        planilha.linhas_ignoradas,
+        progresso,
+    )
+    .await
+}
+
+/// Refaz a interpretação das respostas guardadas em `resultados_raw`, sem
+/// acessar a rede. Serve para preencher campos que o parser passou a
+/// entender depois que os concursos foram baixados.
+pub async fn reprocessar_respostas(
+    db: &DbActor,
+    progresso: &(dyn Fn(usize, usize) + Sync),
+) -> Result<ResumoImportacao, MegaSenaError> {
+    let respostas = db
+        .executar_async(|db| db.listar_respostas_brutas())
+        .await??;
+    let (resultados, ignoradas) =
+        em_segundo_plano(move || Ok(interpretar_respostas(respostas))).await?;
+
+    gravar_em_lotes(db, resultados, ignoradas, progresso).await
+}
+
+/// Resultados das respostas legíveis e quantidade de respostas ignoradas
+fn interpretar_respostas(respostas: Vec<RespostaBruta>) -> (Vec<Resultado>, usize) {
+    let mut resultados = Vec::with_capacity(respostas.len());
+    let mut ignoradas = 0;
+    for resposta in respostas {
+        match api::interpretar_payload(&resposta.payload) {
+            Ok(mut resultado) if resultado.concurso == resposta.concurso => {
+                resultado.fonte = Some(resposta.fonte);
+                resultado.fetched_at = resposta.fetched_at;
+                resultado.provisional = resposta.provisional;
+                resultados.push(resultado);
+            }
+            Ok(resultado) => {
+                eprintln!(
+                    "Resposta de '{}' para o concurso {} traz o concurso {}",
+                    resposta.fonte, resposta.concurso, resultado.concurso
+                );
+                ignoradas += 1;
+            }
+            Err(e) => {
+                eprintln!(
+                    "Resposta de '{}' para o concurso {} ignorada: {}",
+                    resposta.fonte, resposta.concurso, e
+                );
+                ignoradas += 1;
+            }
+        }
+    }
+    (resultados, ignoradas)
+}
+
+/// Cada lote é gravado numa transação própria. Uma falha no meio deixa os
+/// lotes anteriores gravados; como a importação é idempotente, basta repeti-la.
+/// O total do resumo inclui as `ignoradas`; o do progresso, só os resultados.
+async fn gravar_em_lotes(
+    db: &DbActor,
+    mut resultados: Vec<Resultado>,
+    ignoradas: usize,
+    progresso: &(dyn Fn(usize, usize) + Sync),
+) -> Result<ResumoImportacao, MegaSenaError> {
+    let total = resultados.len();
+    let mut resumo = ResumoImportacao {
+        total: total + ignoradas,
+        linhas_ignoradas: ignoradas,
+        ..Default::default()
+    };
+
+    while !resultados.is_empty() {
+        let resto = resultados.split_off(resultados.len().min(TAMANHO_LOTE));
+        let lote = std::mem::replace(&mut resultados, resto);
+        let parcial = db
+            .executar_async(move |db| db.salvar_resultados_lote(&lote))
+            .await??;
+        resumo.novos += parcial.novos;
+        resumo.atualizados += parcial.atualizados;
+        resumo.inalterados += parcial.inalterados;
+        progresso(total - resultados.len(), total);
+    }
+
+    Ok(resumo)
+}
+
+async fn em_segundo_plano<T, F>(tarefa: F) -> Result<T, MegaSenaError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, MegaSenaError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(tarefa)
+        .await
+        .map_err(|e| MegaSenaError::Arquivo(e.to_string()))?
+}
+
+/// O download antigo da Caixa vem em Windows-1252; bytes fora de UTF-8 só
+/// aparecem em nomes de cidades e cabeçalhos acentuados, então a conversão
+/// com perda é suficiente
+fn ler_texto(caminho: &Path) -> Result<String, MegaSenaError> {
+    let bytes = std::fs::read(caminho)
+        .map_err(|e| MegaSenaError::Arquivo(format!("{:?}: {}", caminho, e)))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn ler_xlsx(caminho: &Path) -> Result<Vec<Vec<String>>, String> {
+    let mut planilha = open_workbook_auto(caminho).map_err(|e| e.to_string())?;
+    let intervalo = planilha
+        .worksheet_range_at(0)
+        .ok_or("Planilha sem abas")?
+        .map_err(|e| e.to_string())?;
+
+    Ok(intervalo
+        .rows()
+        .map(|linha| linha.iter().map(texto_celula).collect())
+        .collect())
+}
+
+fn texto_celula(celula: &Data) -> String {
+    match celula {
+        Data::String(s) | Data::DateTimeIso(s) | Data::DurationIso(s) => s.trim().to_string(),
+        Data::Int(i) => i.to_string(),
+        Data::Float(f) if f.fract() == 0.0 => format!("{}", *f as i64),
+        Data::Float(f) => f.to_string(),
+        Data::Bool(b) => b.to_string(),
+        Data::DateTime(d) => data_serial_excel(d.as_f64()),
+        Data::Error(_) | Data::Empty => String::new(),
+    }
+}
+
+/// Datas do Excel são dias desde 30/12/1899
+fn data_serial_excel(serial: f64) -> String {
+    let base = NaiveDate::from_ymd_opt(1899, 12, 30).expect("data base válida");
+    (base + Duration::days(serial as i64))
+        .format("%d/%m/%Y")
+        .to_string()
+}
+
+fn ler_csv(conteudo: &str) -> Result<Vec<Vec<String>>, String> {
+    let primeira_linha = conteudo.lines().next().unwrap_or_default();
+    let delimitador = if primeira_linha.matches(';').count() > primeira_linha.matches(',').count() {
+        b';'
+    } else {
+        b','
+    };
+
+    csv::ReaderBuilder::new()
+        .delimiter(delimitador)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(conteudo.as_bytes())
+        .records()
+        .map(|registro| {
+            registro
+                .map(|r| r.iter().map(|c| c.trim().to_string()).collect())
+                .map_err(|e| e.to_string())
+        })
+        .collect()
+}
+
+/// Extrai as células de cada `<tr>` de uma página HTML simples, como a
+/// exportada pela Caixa. Não pretende ser um parser HTML completo.
+fn ler_html(conteudo: &str) -> Vec<Vec<String>> {
+    // `to_ascii_lowercase` preserva as posições em bytes
+    let minusculo = conteudo.to_ascii_lowercase();
+    let inicios_linhas: Vec<usize> = minusculo.match_indices("<tr").map(|(i, _)| i).collect();
+
+    inicios_linhas
+        .iter()
+        .enumerate()
+        .map(|(n, &inicio)| {
+            let fim = inicios_linhas
+                .get(n + 1)
+                .copied()
+                .unwrap_or(minusculo.len());
+            celulas_html(&conteudo[inicio..fim], &minusculo[inicio..fim])
+        })
+        .filter(|celulas| !celulas.is_empty())
+        .collect()
+}
+
+fn celulas_html(linha: &str, minusculo: &str) -> Vec<String> {
+    let mut celulas = Vec::new();
+    let mut pos = 0;
+
+    while let Some(inicio) = proxima_celula(&minusculo[pos..]).map(|i| pos + i) {
+        let Some(abre) = minusculo[inicio..].find('>').map(|i| inicio + i + 1) else {
+            break;
+        };
+        let fecha = minusculo[abre..]
+            .find("</t")
+            .or_else(|| proxima_celula(&minusculo[abre..]))
+            .map(|i| abre + i)
+            .unwrap_or(minusculo.len());
+
+        celulas.push(texto_html(&linha[abre..fecha]));
+        pos = fecha;
+    }
+
+    celulas
+}
+
+fn proxima_celula(trecho: &str) -> Option<usize> {
+    match (trecho.find("<td"), trecho.find("<th")) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Remove tags e decodifica as entidades HTML mais comuns
+fn texto_html(trecho: &str) -> String {
+    let mut texto = String::new();
+    let mut dentro_tag = false;
+    for c in trecho.chars() {
+        match c {
+            '<' => dentro_tag = true,
+            '>' => dentro_tag = false,
+            c if !dentro_tag => texto.push(c),
+            _ => {}
+        }
+    }
+
+    let mut resultado = String::new();
+    let mut resto = texto.as_str();
+    while let Some(i) = resto.find('&') {
+        resultado.push_str(&resto[..i]);
+        resto = &resto[i..];
+        let entidade = resto.find(';').filter(|&f| f <= 8).map(|f| &resto[1..f]);
+        match entidade.and_then(decodificar_entidade) {
+            Some(c) => {
+                resultado.push(c);
+                resto = &resto[entidade.map_or(0, str::len) + 2..];
+            }
+            None => {
+                resultado.push('&');
+                resto = &resto[1..];
+            }
+        }
+    }
+    resultado.push_str(resto);
+
+    resultado.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn decodificar_entidade(nome: &str) -> Option<char> {
+    if let Some(numero) = nome.strip_prefix('#') {
+        return numero.parse::<u32>().ok().and_then(char::from_u32);
+    }
+    Some(match nome {
+        "nbsp" => ' ',
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "ordf" => 'ª',
+        "ordm" => 'º',
+        "aacute" => 'á',
+        "acirc" => 'â',
+        "atilde" => 'ã',
+        "ccedil" => 'ç',
+        "eacute" => 'é',
+        "ecirc" => 'ê',
+        "iacute" => 'í',
+        "oacute" => 'ó',
+        "ocirc" => 'ô',
+        "otilde" => 'õ',
+        "uacute" => 'ú',
+        "Atilde" => 'Ã',
+        _ => return None,
+    })
+}
+
+/// Cabeçalho em minúsculas, sem acentos, com `_` e espaços repetidos
+/// reduzidos a um espaço
+fn normalizar_cabecalho(cabecalho: &str) -> String {
+    cabecalho
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' | 'ª' => 'a',
+            'é' | 'ê' => 'e',
+            'í' => 'i',
+            'ó' | 'ô' | 'õ' | 'º' => 'o',
+            'ú' => 'u',
+            'ç' => 'c',
+            '_' | '/' => ' ',
+            c => c,
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl Colunas {
+    /// Reconhece tanto o layout atual ("Bola1", "Rateio 6 acertos") quanto o
+    /// antigo ("1ª Dezena", "Rateio_Sena")
+    fn do_cabecalho(cabecalho: &[String]) -> Option<Colunas> {
+        let nomes: Vec<String> = cabecalho.iter().map(|c| normalizar_cabecalho(c)).collect();
+        let achar = |teste: &dyn Fn(&str) -> bool| nomes.iter().position(|n| teste(n));
+
+        let concurso = achar(&|n| n == "concurso")?;
+        let data = achar(&|n| n.starts_with("data"))?;
+
+        let mut bolas = [0; 6];
+        for (i, bola) in bolas.iter_mut().enumerate() {
+            let numero = i + 1;
+            *bola = achar(&|n| {
+                n == format!("bola{}", numero)
+                    || n == format!("bola {}", numero)
+                    || n == format!("{}a dezena", numero)
+                    || n == format!("dezena{}", numero)
+                    || n == format!("dezena {}", numero)
+            })?;
+        }
+
+        let faixas = [
+            ("6 acertos", "sena"),
+            ("5 acertos", "quina"),
+            ("4 acertos", "quadra"),
+        ];
+        let coluna_faixa = |prefixo: &str, (acertos, nome): (&str, &str)| {
+            achar(&|n| n.starts_with(prefixo) && (n.contains(acertos) || n.ends_with(nome)))
+        };
+
+        Some(Colunas {
+            concurso,
+            data,
+            bolas,
+            ganhadores: faixas.map(|f| coluna_faixa("ganhadores", f)),
+            rateio: faixas.map(|f| coluna_faixa("rateio", f)),
+            valor_acumulado: achar(&|n| {
+                (n == "valor acumulado" || n == "acumulado 6 acertos") && !n.contains("virada")
+            }),
+            estimativa: achar(&|n| n.starts_with("estimativa")),
+        })
+    }
+
+    fn converter(&self, linha: &[String]) -> Option<Resultado> {
+        let celula = |i: usize| linha.get(i).map(String::as_str).unwrap_or_default();
+        let opcional = |i: Option<usize>| i.map(celula);
+
+        let concurso: i32 = celula(self.concurso).parse().ok()?;
+        let data_sorteio = normalizar_data(celula(self.data))?;
+
+        let mut numeros_sorteados = self
+            .bolas
+            .iter()
+            .map(|&i| {
+                celula(i)
+                    .parse::<i32>()
+                    .ok()
+                    .filter(|n| (1..=60).contains(n))
+            })
+            .collect::<Option<Vec<i32>>>()?;
+        numeros_sorteados.sort_unstable();
+        numeros_sorteados.dedup();
+        if numeros_sorteados.len() != 6 {
+            return None;
+        }
+
+        let faixas: Vec<FaixaPremio> = (0..3)
+            .filter_map(|i| {
+                Some(FaixaPremio {
+                    faixa: i as i32 + 1,
+                    acertos: 6 - i as i32,
+                    ganhadores: opcional(self.ganhadores[i])?.parse().ok()?,
+                    valor_premio: parse_moeda(opcional(self.rateio[i])?)?,
+                })
+            })
+            .collect();
+
+        // Mesmas regras da conversão da API (api::converter_resposta)
+        let sena = faixas.iter().find(|f| f.faixa == 1);
+        let ganhadores = sena.map(|s| s.ganhadores);
+        let valor_premio = sena.map(|s| s.valor_premio);
+        let valor_acumulado = opcional(self.valor_acumulado).and_then(parse_moeda);
+        let estimativa = opcional(self.estimativa).and_then(parse_moeda);
+
+        let valor_total = match (ganhadores, valor_premio) {
+            (Some(g), Some(v)) if g > 0 => Some(g as f64 * v),
+            (Some(_), Some(_)) => valor_acumulado.or(estimativa),
+            _ => estimativa,
+        };
+
+        Some(Resultado {
+            concurso,
+            numeros_sorteados,
+            data_sorteio,
+            acumulado: ganhadores == Some(0),
+            valor_premio,
+            ganhadores,
+            valor_total,
+            faixas,
+            // A planilha é publicada pela própria Caixa
+            fonte: Some(FONTE_PLANILHA.to_string()),
+            fetched_at: None,
+            provisional: false,
+            payload: None,
+        })
+    }
+}
+
+fn converter_linhas(linhas: &[Vec<String>]) -> Result<Planilha, String> {
+    let (posicao_cabecalho, colunas) = linhas
+        .iter()
+        .enumerate()
+        .find_map(|(i, linha)| Colunas::do_cabecalho(linha).map(|c| (i, c)))
+        .ok_or("Cabeçalho com Concurso, Data e as seis dezenas não encontrado")?;
+
+    let mut planilha = Planilha::default();
+    for linha in &linhas[posicao_cabecalho + 1..] {
+        // Linhas de continuação (cidades dos ganhadores no HTML) não têm concurso
+        let tem_concurso = linha
+            .get(colunas.concurso)
+            .is_some_and(|c| c.parse::<i32>().is_ok());
+        if !tem_concurso {
+            continue;
+        }
+
+        match colunas.converter(linha) {
+            Some(resultado) => planilha.resultados.push(resultado),
+            None => planilha.linhas_ignoradas += 1,
+        }
+    }
+
+    planilha.resultados.sort_by_key(|r| r.concurso);
+    planilha.resultados.dedup_by_key(|r| r.concurso);
+    Ok(planilha)
+}
+
+/// Aceita "dd/mm/aaaa" (formato da API) e "aaaa-mm-dd", sempre devolvendo o
+/// primeiro para que reimportar não altere resultados vindos da API
+fn normalizar_data(texto: &str) -> Option<String> {
+    let texto = texto.split_whitespace().next()?;
+    NaiveDate::parse_from_str(texto, "%d/%m/%Y")
+        .or_else(|_| NaiveDate::parse_from_str(texto, "%Y-%m-%d"))
+        .ok()
+        .map(|d| d.format("%d/%m/%Y").to_string())
+}
+
+/// Valores como "R$1.234.567,89", "1234567,89" ou "1234567.89"
+fn parse_moeda(texto: &str) -> Option<f64> {
+    let limpo: String = texto
+        .chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, ',' | '.' | '-'))
+        .collect();
+    if limpo.is_empty() {
+        return None;
+    }
+
+    let normalizado = if limpo.contains(',') {
+        limpo.replace('.', "").replace(',', ".")
+    } else {
+        limpo
+    };
+    normalizado.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::domain::{Concurso, Dezenas, QuantidadeConcursos};
+    use std::sync::Mutex;
+
+    const CSV_CAIXA: &str = "\
+Concurso;Data do Sorteio;Bola1;Bola2;Bola3;Bola4;Bola5;Bola6;Ganhadores 6 acertos;Cidade / UF;Rateio 6 acertos;Ganhadores 5 acertos;Rateio 5 acertos;Ganhadores 4 acertos;Rateio 4 acertos;Acumulado 6 acertos;Arrecadação Total;Estimativa prêmio;Acumulado Sorte Grande Virada;Observação
+1;11/03/1996;41;5;4;52;30;33;0;;R$0,00;17;R$39.158,92;2016;R$330,21;R$1.714.650,23;R$0,00;R$0,00;R$0,00;
+2;18/03/1996;9;39;37;49;43;41;1;PR;R$2.307.162,23;65;R$14.424,02;4488;R$208,91;R$0,00;R$0,00;R$0,00;R$0,00;
+3;25/03/1996;10;77;x;1;2;3;0;;R$0,00;0;R$0,00;0;R$0,00;R$0,00;R$0,00;R$0,00;R$0,00;
+";
+
+    const HTML_CAIXA: &str = r#"<html><body><table>
+<tr><th>Concurso</th><th>Data Sorteio</th><th>1&ordf; Dezena</th><th>2&ordf; Dezena</th>
+<th>3&ordf; Dezena</th><th>4&ordf; Dezena</th><th>5&ordf; Dezena</th><th>6&ordf; Dezena</th>
+<th>Ganhadores_Sena</th><th>Cidade</th><th>Rateio_Sena</th><th>Ganhadores_Quina</th>
+<th>Rateio_Quina</th><th>Ganhadores_Quadra</th><th>Rateio_Quadra</th><th>Acumulado</th>
+<th>Valor_Acumulado</th><th>Estimativa_Pr&ecirc;mio</th></tr>
+<tr><td rowspan="2">2</td><td rowspan="2">18/03/1996</td><td>09</td><td>39</td><td>37</td>
+<td>49</td><td>43</td><td>41</td><td>2</td><td>CURITIBA</td><td>1.153.581,11</td><td>65</td>
+<td>14.424,02</td><td>4488</td><td>208,91</td><td>N&Atilde;O</td><td>0,00</td><td>0,00</td></tr>
+<tr><td>S&Atilde;O PAULO</td></tr>
+</table></body></html>"#;
+
+    #[test]
+    fn test_csv_layout_atual() {
+        let planilha = converter_linhas(&ler_csv(CSV_CAIXA).unwrap()).unwrap();
+        assert_eq!(planilha.resultados.len(), 2);
+        assert_eq!(planilha.linhas_ignoradas, 1);
+
+        let primeiro = &planilha.resultados[0];
+        assert_eq!(primeiro.numeros_sorteados, vec![4, 5, 30, 33, 41, 52]);
+        assert_eq!(primeiro.data_sorteio, "11/03/1996");
+        assert!(primeiro.acumulado);
+        assert_eq!(primeiro.valor_total, Some(1_714_650.23));
+        assert_eq!(primeiro.faixas.len(), 3);
+        assert_eq!(primeiro.faixas[1].valor_premio, 39_158.92);
+        assert_eq!(primeiro.faixas[2].acertos, 4);
+
+        let segundo = &planilha.resultados[1];
+        assert!(!segundo.acumulado);
+        assert_eq!(segundo.ganhadores, Some(1));
+        assert_eq!(segundo.valor_total, Some(2_307_162.23));
+    }
+
+    #[test]
+    fn test_html_layout_antigo() {
+        let planilha = converter_linhas(&ler_html(HTML_CAIXA)).unwrap();
+        assert_eq!(planilha.resultados.len(), 1);
+        assert_eq!(planilha.linhas_ignoradas, 0);
+
+        let resultado = &planilha.resultados[0];
+        assert_eq!(resultado.concurso, 2);
+        assert_eq!(resultado.numeros_sorteados, vec![9, 37, 39, 41, 43, 49]);
+        assert_eq!(resultado.ganhadores, Some(2));
+        assert_eq!(resultado.faixas[0].valor_premio, 1_153_581.11);
+        assert_eq!(resultado.faixas[2].ganhadores, 4488);
+    }
+
+    #[test]
+    fn test_parse_moeda() {
+        assert_eq!(parse_moeda("R$1.234.567,89"), Some(1_234_567.89));
+        assert_eq!(parse_moeda("1234567.89"), Some(1_234_567.89));
+        assert_eq!(parse_moeda("R$0,00"), Some(0.0));
+        assert_eq!(parse_moeda(""), None);
+    }
+
+    fn csv_com_concursos(quantidade: i32) -> String {
+        let mut csv = CSV_CAIXA.lines().next().unwrap().to_string();
+        for concurso in 1..=quantidade {
+            csv.push_str(&format!(
+                "\n{};11/03/1996;41;5;4;52;30;33;0;;R$0,00;17;R$39.158,92;2016;R$330,21;R$1.714.650,23;R$0,00;R$0,00;R$0,00;",
+                concurso
+            ));
+        }
+        csv
+    }
+
+    fn arquivo_temporario(nome: &str, conteudo: &str) -> PathBuf {
+        let caminho =
+            std::env::temp_dir().join(format!("megasena-{}-{}.csv", nome, std::process::id()));
+        std::fs::write(&caminho, conteudo).unwrap();
+        caminho
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_importacao_idempotente() {
+        let mut db = Database::new(":memory:".into()).unwrap();
+        db.init().unwrap();
+        let aposta = db
+            .adicionar_aposta(
+                &Dezenas::novas(vec![9, 37, 39, 41, 43, 49]).unwrap(),
+                Concurso::novo(2).unwrap(),
+                QuantidadeConcursos::nova(1).unwrap(),
+            )
+            .unwrap();
+        let db = DbActor::iniciar(db);
+
+        let caminho = arquivo_temporario("importacao", CSV_CAIXA);
+        let andamento = Mutex::new(Vec::new());
+        let resumo = importar_arquivo(&db, caminho.clone(), &|processados, total| {
+            andamento.lock().unwrap().push((processados, total));
+        })
+        .await
+        .unwrap();
+        assert_eq!(resumo.total, 3);
+        assert_eq!(resumo.novos, 2);
+        assert_eq!(resumo.linhas_ignoradas, 1);
+        assert_eq!(andamento.into_inner().unwrap(), vec![(2, 2)]);
+        let acertos = db
+            .executar_async(move |db| db.obter_acertos_aposta(aposta.id))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(acertos.get(&2), Some(&6));
+
+        let resumo = importar_arquivo(&db, caminho.clone(), &|_, _| {})
+            .await
+            .unwrap();
+        std::fs::remove_file(&caminho).unwrap();
+        assert_eq!(resumo.novos, 0);
+        assert_eq!(resumo.atualizados, 0);
+        assert_eq!(resumo.inalterados, 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_importacao_em_lotes() {
+        let mut db = Database::new(":memory:".into()).unwrap();
+        db.init().unwrap();
+        let db = DbActor::iniciar(db);
+
+        let quantidade = TAMANHO_LOTE as i32 * 2 + 1;
+        let caminho = arquivo_temporario("lotes", &csv_com_concursos(quantidade));
+        let andamento = Mutex::new(Vec::new());
+        let resumo = importar_arquivo(&db, caminho.clone(), &|processados, total| {
+            andamento.lock().unwrap().push((processados, total));
+        })
+        .await
+        .unwrap();
+        std::fs::remove_file(&caminho).unwrap();
+
+        let total = quantidade as usize;
+        assert_eq!(resumo.total, total);
+        assert_eq!(resumo.novos, total);
+        assert_eq!(
+            andamento.into_inner().unwrap(),
+            vec![
+                (TAMANHO_LOTE, total),
+                (TAMANHO_LOTE * 2, total),
+                (total, total)
+            ]
+        );
+        let ultimo = db
+            .executar_async(|db| db.obter_ultimo_resultado())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(ultimo.concurso, quantidade);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reprocessar_respostas_brutas() {
+        let mut db = Database::new(":memory:".into()).unwrap();
+        db.init().unwrap();
+
+        let payload = include_str!("../fixtures/megasena/2954.json");
+        let mut resultado = api::interpretar_payload(payload).unwrap();
+        resultado.fonte = Some("caixa".to_string());
+        resultado.payload = Some(payload.to_string());
+        db.salvar_resultado(&resultado).unwrap();
+
+        // Simula um resultado gravado por um parser que ainda não lia o rateio
+        resultado.faixas.clear();
+        resultado.payload = None;
+        db.salvar_resultado(&resultado).unwrap();
+        assert!(db.obter_faixas_resultado(2954).unwrap().is_empty());
+        let db = DbActor::iniciar(db);
+
+        let resumo = reprocessar_respostas(&db, &|_, _| {}).await.unwrap();
+        assert_eq!(resumo.total, 1);
+        assert_eq!(resumo.atualizados, 1);
+        let (faixas, salvo) = db
+            .executar_async(|db| -> rusqlite::Result<_> {
+                Ok((
+                    db.obter_faixas_resultado(2954)?,
+                    db.obter_ultimo_resultado()?,
+                ))
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(faixas.len(), 3);
+        let salvo = salvo.unwrap();
+        assert_eq!(salvo.fonte.as_deref(), Some("caixa"));
+        assert_eq!(salvo.payload.as_deref(), Some(payload));
+
+        // Sem mudanças no parser, reprocessar de novo não altera nada
+        let resumo = reprocessar_respostas(&db, &|_, _| {}).await.unwrap();
+        assert_eq!(resumo.inalterados, 1);
+    }
+}
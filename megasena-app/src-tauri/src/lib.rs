@@ -1,18 +1,132 @@
 // Lib.rs - Main library file for Tauri app
 
+pub mod agendador;
 pub mod api;
+pub mod calendario;
 pub mod commands;
+pub mod config;
 pub mod database;
+pub mod db_actor;
+pub mod domain;
+pub mod error;
+pub mod eventos;
+#[cfg(any(test, feature = "fake-server"))]
+pub mod fake_loteria;
+pub mod importacao;
+pub mod migrations;
 pub mod models;
+pub mod notificacoes;
+pub mod premios;
+pub mod reconciliacao;
+pub mod resiliencia;
+pub mod sincronizacao;
 
+use agendador::Agendador;
+use api::ProviderChain;
+use config::AppConfig;
 use database::Database;
-use std::sync::Mutex;
+use db_actor::DbActor;
 use tauri::{
     menu::{Menu, MenuItem},
     tray::{TrayIconBuilder, TrayIconEvent},
-    Emitter, Manager,
+    AppHandle, Emitter, Manager, Wry,
 };
 
+const TRAY_ID: &str = "principal";
+
+/// Item do menu da bandeja que mostra o próximo sorteio
+struct ItemProximoConcurso(MenuItem<Wry>);
+
+/// Atualiza o próximo concurso e o prêmio estimado no menu e na dica da bandeja
+fn atualizar_proximo_concurso(app: &AppHandle) {
+    let db = app.state::<DbActor>();
+    let providers = app.state::<ProviderChain>();
+    let proximo = match commands::consultar_proximo_concurso(&db, &providers) {
+        Ok(proximo) => proximo,
+        Err(e) => {
+            eprintln!("Próximo concurso indisponível: {}", e);
+            return;
+        }
+    };
+
+    let mut texto = format!("Próximo concurso: {}", proximo.concurso);
+    if let Some(data) = &proximo.data_sorteio {
+        texto.push_str(&format!(" ({})", data));
+    }
+    if let Some(valor) = proximo.valor_estimado {
+        texto.push_str(&format!(" - {}", premios::formatar_moeda(valor)));
+    }
+
+    if let Some(item) = app.try_state::<ItemProximoConcurso>() {
+        let _ = item.0.set_text(&texto);
+    }
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ = tray.set_tooltip(Some(format!("MegaSena Monitor\n{}", texto)));
+    }
+}
+
+/// Confere provisórios, busca os concursos que faltam e notifica os acertos
+fn verificar_novos_resultados(app_handle: &AppHandle) {
+    println!("[Agendador] Verificando novos resultados em background...");
+
+    let providers = app_handle.state::<ProviderChain>();
+
+    // Conferir com a Caixa o que veio de fontes alternativas
+    let db = app_handle.state::<DbActor>();
+    let emitir = |evento: eventos::Evento| evento.emitir(app_handle);
+    let tarefa = reconciliacao::reconciliar_provisorios(&db, &providers, &emitir);
+    match tauri::async_runtime::block_on(tarefa) {
+        Ok(resumo) if !resumo.corrigidos.is_empty() => {
+            let _ = app_handle.emit("novo-resultado", ());
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("[Agendador] Falha na reconciliação: {}", e),
+    }
+
+    // Todos os concursos sorteados que faltam para as apostas, não só o último
+    let tarefa = sincronizacao::sincronizar(&db, &providers, &emitir);
+    let resumo = match tauri::async_runtime::block_on(tarefa) {
+        Ok(resumo) => resumo,
+        Err(e) => {
+            // Sem sincronizar, ainda entrega o que estava na fila
+            eprintln!("[Agendador] Falha na sincronização: {}", e);
+            Default::default()
+        }
+    };
+
+    use tauri_plugin_notification::NotificationExt;
+    // Acertos e conflitos, inclusive os retidos pelo horário de silêncio.
+    // Provedores que divergem geram um alerta em vez de notificar acertos
+    // com dezenas possivelmente erradas, e só uma vez por conflito.
+    let agora = chrono::Local::now().time();
+    let pendentes = db
+        .executar(move |db| notificacoes::entregar_pendentes(db, agora))
+        .and_then(|entrega| entrega.map_err(Into::into));
+    match pendentes {
+        Ok(pendentes) => {
+            for notificacao in pendentes {
+                if let notificacoes::Notificacao::Conflito(verificacao) = &notificacao {
+                    let _ = app_handle.emit("resultado-divergente", verificacao);
+                }
+                let _ = app_handle
+                    .notification()
+                    .builder()
+                    .title(notificacao.titulo())
+                    .body(&notificacao.corpo())
+                    .show();
+            }
+        }
+        Err(e) => eprintln!("[Agendador] Falha ao preparar notificações: {}", e),
+    }
+
+    if resumo.novos.is_empty() {
+        return;
+    }
+
+    let _ = app_handle.emit("novo-resultado", ());
+    atualizar_proximo_concurso(app_handle);
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -31,64 +145,32 @@ pub fn run() {
             std::fs::create_dir_all(&app_dir).expect("failed to create app data dir");
 
             let db_path = app_dir.join("megasena.db");
-            let db = Database::new(db_path).expect("failed to initialize database");
-            db.init().expect("failed to create tables");
+            let mut db = Database::new(db_path).expect("failed to initialize database");
+            db.init().expect("failed to migrate database");
+
+            // Esvaziar da lixeira o que passou do prazo de retenção
+            if let Err(e) = db
+                .obter_retencao_lixeira()
+                .and_then(|dias| db.purgar_lixeira(dias))
+            {
+                eprintln!("Falha ao purgar a lixeira: {}", e);
+            }
 
-            // Gerenciar estado do database
-            app.manage(Mutex::new(db));
+            // O banco passa a viver na thread do ator; comandos e verificações
+            // em segundo plano trocam mensagens com ele
+            app.manage(DbActor::iniciar(db));
 
-            // Iniciar Verificador em Background (Cron)
+            // Cadeia de provedores de resultados (config.json opcional)
+            let config = AppConfig::load(&app_dir.join("config.json"));
+            app.manage(ProviderChain::from_config(&config.api));
+
+            // Verificador em segundo plano: roda já na abertura e depois segue
+            // o calendário de sorteios
+            app.manage(Agendador::new());
             let app_handle = app.handle().clone();
             std::thread::spawn(move || {
-                loop {
-                    std::thread::sleep(std::time::Duration::from_secs(60 * 60)); // 1 hour
-                    println!("[Cron] Verificando novos resultados em background...");
-
-                    if let Ok(ultimo_concurso) = api::obter_ultimo_concurso_numero() {
-                        let db_mutex = app_handle.state::<Mutex<Database>>();
-                        if let Ok(db) = db_mutex.lock() {
-                            if let Ok(None) = db.obter_resultado(ultimo_concurso) {
-                                if let Ok(resultado) = api::verificar_resultado(ultimo_concurso) {
-                                    let _ = db.salvar_resultado(&resultado);
-                                    let _ = db.processar_acertos_concurso(
-                                        ultimo_concurso,
-                                        &resultado.numeros_sorteados,
-                                    );
-
-                                    if let Ok(apostas) = db.listar_apostas() {
-                                        for aposta in apostas {
-                                            if let Some(&acertos) =
-                                                aposta.acertos.get(&ultimo_concurso)
-                                            {
-                                                if acertos >= 4 {
-                                                    use tauri_plugin_notification::NotificationExt;
-                                                    let premio = match acertos {
-                                                        4 => "Quadra",
-                                                        5 => "Quina",
-                                                        6 => "Sena",
-                                                        _ => "",
-                                                    };
-                                                    let msg = format!(
-                                                        "Você acertou uma {} no concurso {}!",
-                                                        premio, ultimo_concurso
-                                                    );
-                                                    let _ = app_handle
-                                                        .notification()
-                                                        .builder()
-                                                        .title("MegaSena Monitor - Você Ganhou! 🍀")
-                                                        .body(&msg)
-                                                        .show();
-                                                }
-                                            }
-                                        }
-                                    }
-
-                                    let _ = app_handle.emit("novo-resultado", ());
-                                }
-                            }
-                        };
-                    }
-                }
+                let agendador = app_handle.state::<Agendador>();
+                agendador.executar(|| verificar_novos_resultados(&app_handle))
             });
 
             // Configurar Menu de Aplicativo (macOS)
@@ -132,12 +214,15 @@ pub fn run() {
             });
 
             // Configurar Tray Icon
+            let t_proximo =
+                MenuItem::with_id(app, "proximo", "Próximo concurso: ...", false, None::<&str>)?;
             let t_mostrar =
                 MenuItem::with_id(app, "mostrar", "Mostrar Monitor", true, None::<&str>)?;
             let t_sair = MenuItem::with_id(app, "quit", "Sair", true, None::<&str>)?;
-            let tray_menu = Menu::with_items(app, &[&t_mostrar, &t_sair])?;
+            let tray_menu = Menu::with_items(app, &[&t_proximo, &t_mostrar, &t_sair])?;
+            app.manage(ItemProximoConcurso(t_proximo));
 
-            let _tray = TrayIconBuilder::new()
+            let _tray = TrayIconBuilder::with_id(TRAY_ID)
                 .icon(app.default_window_icon().unwrap().clone())
                 .menu(&tray_menu)
                 .on_menu_event(|app, event| match event.id.as_ref() {
@@ -165,15 +250,38 @@ pub fn run() {
                 })
                 .build(app)?;
 
+            // Preencher o próximo concurso sem atrasar a abertura da janela
+            let app_handle = app.handle().clone();
+            std::thread::spawn(move || atualizar_proximo_concurso(&app_handle));
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::adicionar_aposta,
+            commands::atualizar_aposta,
             commands::listar_apostas,
             commands::excluir_aposta,
+            commands::arquivar_aposta,
+            commands::desarquivar_aposta,
+            commands::restaurar_aposta,
+            commands::obter_retencao_lixeira,
+            commands::definir_retencao_lixeira,
+            commands::silenciar_aposta,
+            commands::obter_preferencias_notificacao,
+            commands::definir_preferencias_notificacao,
             commands::verificar_resultados,
             commands::carregar_ultimos_resultados,
             commands::obter_ultimo_concurso,
+            commands::obter_proximo_concurso,
+            commands::diagnostico_provedores,
+            commands::listar_verificacoes,
+            commands::reconciliar_resultados,
+            commands::obter_premios_aposta,
+            commands::importar_resultados,
+            commands::reprocessar_resultados,
+            commands::obter_proxima_verificacao,
+            commands::verificar_agora,
+            commands::sincronizar_resultados,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
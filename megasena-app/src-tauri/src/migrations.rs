@@ -0,0 +1,626 @@
+/*
+ * MegaSena Monitor - Minimalist desktop application for managing bets.
+ * Copyright (C) 2025 Zander Cattapreta
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Versioned schema migrations tracked in `PRAGMA user_version`
+
+use rusqlite::{Connection, Transaction};
+use std::fmt;
+
+pub struct Migracao {
+    pub versao: i32,
+    pub descricao: &'static str,
+    pub aplicar: fn(&Transaction) -> rusqlite::Result<()>,
+}
+
+#[derive(Debug)]
+pub enum MigrationError {
+    Sqlite(rusqlite::Error),
+    /// O banco foi criado por uma versão mais nova do aplicativo
+    VersaoMaisNova {
+        banco: i32,
+        suportada: i32,
+    },
+    Falhou {
+        versao: i32,
+        erro: rusqlite::Error,
+    },
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::Sqlite(e) => write!(f, "Erro no banco de dados: {}", e),
+            MigrationError::VersaoMaisNova { banco, suportada } => write!(
+                f,
+                "Banco de dados na versão {} é mais novo que o suportado ({}). Atualize o aplicativo.",
+                banco, suportada
+            ),
+            MigrationError::Falhou { versao, erro } => {
+                write!(f, "Migração {} falhou: {}", versao, erro)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+impl From<rusqlite::Error> for MigrationError {
+    fn from(e: rusqlite::Error) -> Self {
+        MigrationError::Sqlite(e)
+    }
+}
+
+/// Lista ordenada de migrações. Nunca altere uma migração já publicada:
+/// adicione uma nova com a próxima versão.
+pub const MIGRACOES: &[Migracao] = &[
+    Migracao {
+        versao: 1,
+        descricao: "esquema inicial",
+        aplicar: m001_esquema_inicial,
+    },
+    Migracao {
+        versao: 2,
+        descricao: "faixas de premiação por concurso",
+        aplicar: m002_resultados_faixas,
+    },
+    Migracao {
+        versao: 3,
+        descricao: "prêmios por aposta",
+        aplicar: m003_apostas_premios,
+    },
+    Migracao {
+        versao: 4,
+        descricao: "contagem de apostas simples por faixa",
+        aplicar: m004_contagem_faixas,
+    },
+    Migracao {
+        versao: 5,
+        descricao: "dezenas normalizadas em tabelas próprias",
+        aplicar: m005_dezenas_normalizadas,
+    },
+    Migracao {
+        versao: 6,
+        descricao: "lixeira de apostas e configurações",
+        aplicar: m006_lixeira_configuracoes,
+    },
+    Migracao {
+        versao: 7,
+        descricao: "verificação cruzada de resultados",
+        aplicar: m007_verificacoes_resultados,
+    },
+    Migracao {
+        versao: 8,
+        descricao: "procedência dos resultados",
+        aplicar: m008_procedencia_resultados,
+    },
+    Migracao {
+        versao: 9,
+        descricao: "respostas brutas dos provedores",
+        aplicar: m009_resultados_raw,
+    },
+    Migracao {
+        versao: 10,
+        descricao: "regras de notificação",
+        aplicar: m010_notificacoes,
+    },
+    Migracao {
+        versao: 11,
+        descricao: "alerta único de conflitos",
+        aplicar: m011_conflitos_alertados,
+    },
+    Migracao {
+        versao: 12,
+        descricao: "apostas legadas com dezenas inválidas",
+        aplicar: m012_apostas_invalidas,
+    },
+];
+
+pub fn versao_atual(conn: &Connection) -> rusqlite::Result<i32> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+/// Aplica as migrações pendentes, cada uma em sua própria transação
+pub fn migrar(conn: &mut Connection) -> Result<(), MigrationError> {
+    aplicar_migracoes(conn, MIGRACOES)
+}
+
+fn aplicar_migracoes(conn: &mut Connection, migracoes: &[Migracao]) -> Result<(), MigrationError> {
+    let atual = versao_atual(conn)?;
+    let suportada = migracoes.last().map(|m| m.versao).unwrap_or(0);
+
+    if atual > suportada {
+        return Err(MigrationError::VersaoMaisNova {
+            banco: atual,
+            suportada,
+        });
+    }
+
+    for migracao in migracoes.iter().filter(|m| m.versao > atual) {
+        let tx = conn.transaction()?;
+        (migracao.aplicar)(&tx).map_err(|erro| MigrationError::Falhou {
+            versao: migracao.versao,
+            erro,
+        })?;
+        tx.pragma_update(None, "user_version", migracao.versao)?;
+        tx.commit()?;
+
+        println!(
+            "Migração {} aplicada: {}",
+            migracao.versao, migracao.descricao
+        );
+    }
+
+    Ok(())
+}
+
+fn coluna_existe(tx: &Transaction, tabela: &str, coluna: &str) -> rusqlite::Result<bool> {
+    let mut stmt = tx.prepare(&format!("PRAGMA table_info({})", tabela))?;
+    let colunas = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(colunas.iter().any(|c| c == coluna))
+}
+
+/// Bancos anteriores ao controle de versão podem já ter parte das colunas
+fn adicionar_coluna(
+    tx: &Transaction,
+    tabela: &str,
+    coluna: &str,
+    definicao: &str,
+) -> rusqlite::Result<()> {
+    if !coluna_existe(tx, tabela, coluna)? {
+        tx.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", tabela, coluna, definicao),
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+fn m001_esquema_inicial(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS apostas (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            numeros TEXT NOT NULL,
+            concurso_inicial INTEGER NOT NULL,
+            quantidade_concursos INTEGER NOT NULL,
+            data_criacao DATETIME DEFAULT CURRENT_TIMESTAMP,
+            ativa BOOLEAN DEFAULT 1
+        );
+
+        CREATE TABLE IF NOT EXISTS resultados (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            concurso INTEGER NOT NULL UNIQUE,
+            numeros_sorteados TEXT NOT NULL,
+            data_sorteio DATE,
+            acumulado BOOLEAN,
+            data_verificacao DATETIME DEFAULT CURRENT_TIMESTAMP
+        );
+
+        CREATE TABLE IF NOT EXISTS apostas_resultados (
+            aposta_id INTEGER,
+            concurso INTEGER,
+            acertos INTEGER,
+            PRIMARY KEY (aposta_id, concurso),
+            FOREIGN KEY (aposta_id) REFERENCES apostas(id) ON DELETE CASCADE,
+            FOREIGN KEY (concurso) REFERENCES resultados(concurso) ON DELETE CASCADE
+        );",
+    )?;
+
+    adicionar_coluna(tx, "resultados", "valor_premio", "REAL")?;
+    adicionar_coluna(tx, "resultados", "ganhadores", "INTEGER")?;
+    adicionar_coluna(tx, "resultados", "valor_total", "REAL")?;
+    Ok(())
+}
+
+fn m002_resultados_faixas(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS resultados_faixas (
+            concurso INTEGER NOT NULL,
+            faixa INTEGER NOT NULL,
+            acertos INTEGER NOT NULL,
+            ganhadores INTEGER NOT NULL,
+            valor_premio REAL NOT NULL,
+            PRIMARY KEY (concurso, faixa),
+            FOREIGN KEY (concurso) REFERENCES resultados(concurso) ON DELETE CASCADE
+        );",
+    )
+}
+
+fn m003_apostas_premios(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS apostas_premios (
+            aposta_id INTEGER NOT NULL,
+            concurso INTEGER NOT NULL,
+            faixa INTEGER NOT NULL,
+            acertos INTEGER NOT NULL,
+            quantidade INTEGER NOT NULL,
+            valor_unitario REAL NOT NULL,
+            valor_total REAL NOT NULL,
+            PRIMARY KEY (aposta_id, concurso, faixa),
+            FOREIGN KEY (aposta_id) REFERENCES apostas(id) ON DELETE CASCADE,
+            FOREIGN KEY (concurso) REFERENCES resultados(concurso) ON DELETE CASCADE
+        );",
+    )
+}
+
+fn m004_contagem_faixas(tx: &Transaction) -> rusqlite::Result<()> {
+    for coluna in ["senas", "quinas", "quadras"] {
+        adicionar_coluna(
+            tx,
+            "apostas_resultados",
+            coluna,
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
+    }
+    Ok(())
+}
+
+/// Formato legado da coluna de dezenas: JSON ("[1,2,3]") ou lista separada por vírgulas
+fn parse_dezenas_legado(texto: &str) -> Vec<i32> {
+    serde_json::from_str(texto).unwrap_or_else(|_| {
+        texto
+            .split(',')
+            .filter_map(|s| s.trim().parse().ok())
+            .collect()
+    })
+}
+
+fn m005_dezenas_normalizadas(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS aposta_dezenas (
+            aposta_id INTEGER NOT NULL,
+            dezena INTEGER NOT NULL,
+            PRIMARY KEY (aposta_id, dezena),
+            FOREIGN KEY (aposta_id) REFERENCES apostas(id) ON DELETE CASCADE
+        );
+        CREATE INDEX IF NOT EXISTS idx_aposta_dezenas_dezena ON aposta_dezenas(dezena);
+
+        CREATE TABLE IF NOT EXISTS resultado_dezenas (
+            concurso INTEGER NOT NULL,
+            dezena INTEGER NOT NULL,
+            PRIMARY KEY (concurso, dezena),
+            FOREIGN KEY (concurso) REFERENCES resultados(concurso) ON DELETE CASCADE
+        );
+        CREATE INDEX IF NOT EXISTS idx_resultado_dezenas_dezena ON resultado_dezenas(dezena);",
+    )?;
+
+    let apostas = {
+        let mut stmt = tx.prepare("SELECT id, numeros FROM apostas")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows
+    };
+    for (id, numeros) in apostas {
+        for dezena in parse_dezenas_legado(&numeros) {
+            tx.execute(
+                "INSERT OR IGNORE INTO aposta_dezenas (aposta_id, dezena) VALUES (?1, ?2)",
+                rusqlite::params![id, dezena],
+            )?;
+        }
+    }
+
+    let resultados = {
+        let mut stmt = tx.prepare("SELECT concurso, numeros_sorteados FROM resultados")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows
+    };
+    for (concurso, numeros) in resultados {
+        for dezena in parse_dezenas_legado(&numeros) {
+            tx.execute(
+                "INSERT OR IGNORE INTO resultado_dezenas (concurso, dezena) VALUES (?1, ?2)",
+                rusqlite::params![concurso, dezena],
+            )?;
+        }
+    }
+
+    tx.execute_batch(
+        "ALTER TABLE apostas DROP COLUMN numeros;
+        ALTER TABLE resultados DROP COLUMN numeros_sorteados;",
+    )
+}
+
+fn m006_lixeira_configuracoes(tx: &Transaction) -> rusqlite::Result<()> {
+    adicionar_coluna(tx, "apostas", "excluida_em", "DATETIME")?;
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS configuracoes (
+            chave TEXT PRIMARY KEY,
+            valor TEXT NOT NULL
+        );",
+    )
+}
+
+/// Sem chave estrangeira para `resultados`: um concurso em conflito é
+/// registrado aqui justamente por não ter sido gravado lá
+fn m007_verificacoes_resultados(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS resultados_verificacoes (
+            concurso INTEGER PRIMARY KEY,
+            status TEXT NOT NULL,
+            provedores TEXT NOT NULL,
+            divergencias TEXT NOT NULL,
+            verificado_em DATETIME DEFAULT CURRENT_TIMESTAMP
+        );",
+    )
+}
+
+/// Resultados anteriores ficam com fonte desconhecida e não provisórios
+fn m008_procedencia_resultados(tx: &Transaction) -> rusqlite::Result<()> {
+    adicionar_coluna(tx, "resultados", "fonte", "TEXT")?;
+    adicionar_coluna(tx, "resultados", "fetched_at", "DATETIME")?;
+    adicionar_coluna(
+        tx,
+        "resultados",
+        "provisional",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    tx.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_resultados_provisional
+         ON resultados(provisional) WHERE provisional = 1;",
+    )
+}
+
+/// Último corpo JSON recebido de cada provedor por concurso, para
+/// reprocessar sem buscar de novo
+fn m009_resultados_raw(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS resultados_raw (
+            concurso INTEGER NOT NULL,
+            fonte TEXT NOT NULL,
+            payload TEXT NOT NULL,
+            fetched_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY (concurso, fonte)
+        );",
+    )
+}
+
+/// Concursos a notificar; `enviada_em` fica nulo enquanto a notificação
+/// aguarda, por exemplo, o fim do horário de silêncio
+fn m010_notificacoes(tx: &Transaction) -> rusqlite::Result<()> {
+    adicionar_coluna(tx, "apostas", "silenciada", "INTEGER NOT NULL DEFAULT 0")?;
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS notificacoes_concursos (
+            concurso INTEGER PRIMARY KEY,
+            registrada_em DATETIME DEFAULT CURRENT_TIMESTAMP,
+            enviada_em DATETIME
+        );",
+    )
+}
+
+/// Conflitos já anunciados ao usuário; volta a nulo quando o status muda
+fn m011_conflitos_alertados(tx: &Transaction) -> rusqlite::Result<()> {
+    adicionar_coluna(tx, "resultados_verificacoes", "alertada_em", "DATETIME")
+}
+
+/// Antes da validação completa só se conferia a quantidade de dezenas, e a
+/// m005 descartou as repetidas. Apostas que ficaram fora do volante ou com
+/// menos de 6 dezenas são arquivadas e marcadas em `invalida`; as dezenas
+/// são mantidas como estavam e a aposta deixa de ser conferida.
+fn m012_apostas_invalidas(tx: &Transaction) -> rusqlite::Result<()> {
+    adicionar_coluna(tx, "apostas", "invalida", "INTEGER NOT NULL DEFAULT 0")?;
+    let marcadas = tx.execute(
+        "UPDATE apostas SET ativa = 0, invalida = 1
+         WHERE EXISTS (
+             SELECT 1 FROM aposta_dezenas
+             WHERE aposta_id = apostas.id AND dezena NOT BETWEEN 1 AND 60
+         )
+         OR (SELECT COUNT(*) FROM aposta_dezenas WHERE aposta_id = apostas.id)
+            NOT BETWEEN 6 AND 20",
+        [],
+    )?;
+    if marcadas > 0 {
+        eprintln!("{} aposta(s) com dezenas inválidas arquivada(s)", marcadas);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nova_conexao() -> Connection {
+        Connection::open_in_memory().unwrap()
+    }
+
+    #[test]
+    fn test_versoes_sequenciais() {
+        for (i, migracao) in MIGRACOES.iter().enumerate() {
+            assert_eq!(migracao.versao, i as i32 + 1);
+        }
+    }
+
+    #[test]
+    fn test_banco_novo_chega_na_ultima_versao() {
+        let mut conn = nova_conexao();
+        migrar(&mut conn).unwrap();
+        assert_eq!(
+            versao_atual(&conn).unwrap(),
+            MIGRACOES.last().unwrap().versao
+        );
+
+        // Rodar de novo é inofensivo
+        migrar(&mut conn).unwrap();
+    }
+
+    #[test]
+    fn test_banco_legado_sem_versao() {
+        let mut conn = nova_conexao();
+        // Esquema criado pelas versões anteriores ao controle de versão
+        conn.execute_batch(
+            "CREATE TABLE apostas (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                numeros TEXT NOT NULL,
+                concurso_inicial INTEGER NOT NULL,
+                quantidade_concursos INTEGER NOT NULL,
+                data_criacao DATETIME DEFAULT CURRENT_TIMESTAMP,
+                ativa BOOLEAN DEFAULT 1
+            );
+            CREATE TABLE resultados (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                concurso INTEGER NOT NULL UNIQUE,
+                numeros_sorteados TEXT NOT NULL,
+                data_sorteio DATE,
+                acumulado BOOLEAN,
+                valor_premio REAL,
+                data_verificacao DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+            INSERT INTO apostas (numeros, concurso_inicial, quantidade_concursos)
+            VALUES ('[1,2,3,4,5,6]', 2650, 1);
+            INSERT INTO apostas (numeros, concurso_inicial, quantidade_concursos)
+            VALUES ('7, 8, 9, 10, 11, 12, 13', 2650, 1);
+            INSERT INTO resultados (concurso, numeros_sorteados, data_sorteio, acumulado)
+            VALUES (2650, '[3,8,15,22,41,60]', '01/11/2023', 1);",
+        )
+        .unwrap();
+
+        migrar(&mut conn).unwrap();
+
+        let total: i32 = conn
+            .query_row("SELECT COUNT(*) FROM apostas", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(total, 2);
+
+        // Dezenas legadas (JSON e separadas por vírgula) convertidas
+        let dezenas = |sql: &str| -> i32 { conn.query_row(sql, [], |r| r.get(0)).unwrap() };
+        assert_eq!(
+            dezenas("SELECT COUNT(*) FROM aposta_dezenas WHERE aposta_id = 1"),
+            6
+        );
+        assert_eq!(
+            dezenas("SELECT COUNT(*) FROM aposta_dezenas WHERE aposta_id = 2"),
+            7
+        );
+        assert_eq!(
+            dezenas("SELECT SUM(dezena) FROM resultado_dezenas WHERE concurso = 2650"),
+            149
+        );
+
+        let tx = conn.transaction().unwrap();
+        assert!(coluna_existe(&tx, "resultados", "valor_total").unwrap());
+        assert!(coluna_existe(&tx, "apostas_resultados", "quadras").unwrap());
+        assert!(!coluna_existe(&tx, "apostas", "numeros").unwrap());
+        assert!(!coluna_existe(&tx, "resultados", "numeros_sorteados").unwrap());
+    }
+
+    #[test]
+    fn test_banco_legado_com_aposta_invalida() {
+        let mut conn = nova_conexao();
+        conn.execute_batch(
+            "CREATE TABLE apostas (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                numeros TEXT NOT NULL,
+                concurso_inicial INTEGER NOT NULL,
+                quantidade_concursos INTEGER NOT NULL,
+                data_criacao DATETIME DEFAULT CURRENT_TIMESTAMP,
+                ativa BOOLEAN DEFAULT 1
+            );
+            CREATE TABLE resultados (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                concurso INTEGER NOT NULL UNIQUE,
+                numeros_sorteados TEXT NOT NULL,
+                data_sorteio DATE,
+                acumulado BOOLEAN,
+                valor_premio REAL,
+                data_verificacao DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+            INSERT INTO apostas (numeros, concurso_inicial, quantidade_concursos)
+            VALUES ('[1,2,3,4,5,6]', 2650, 1);
+            INSERT INTO apostas (numeros, concurso_inicial, quantidade_concursos)
+            VALUES ('[1,1,2,3,4,5]', 2650, 1);
+            INSERT INTO apostas (numeros, concurso_inicial, quantidade_concursos)
+            VALUES ('[0,7,8,9,10,99]', 2650, 1);",
+        )
+        .unwrap();
+
+        migrar(&mut conn).unwrap();
+
+        let marcadas = conn
+            .prepare("SELECT id FROM apostas WHERE invalida = 1 AND ativa = 0 ORDER BY id")
+            .unwrap()
+            .query_map([], |r| r.get::<_, i64>(0))
+            .unwrap()
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(marcadas, vec![2, 3]);
+
+        // As dezenas ficam como estavam
+        let dezenas: i32 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM aposta_dezenas WHERE aposta_id = 3",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(dezenas, 6);
+    }
+
+    #[test]
+    fn test_recusa_banco_mais_novo() {
+        let mut conn = nova_conexao();
+        conn.pragma_update(None, "user_version", 999).unwrap();
+
+        match migrar(&mut conn) {
+            Err(MigrationError::VersaoMaisNova { banco, .. }) => assert_eq!(banco, 999),
+            outro => panic!("esperava VersaoMaisNova, obteve {:?}", outro),
+        }
+    }
+
+    #[test]
+    fn test_migracao_com_erro_nao_altera_versao() {
+        fn cria_tabela(tx: &Transaction) -> rusqlite::Result<()> {
+            tx.execute_batch("CREATE TABLE t (id INTEGER)")
+        }
+        fn falha_no_meio(tx: &Transaction) -> rusqlite::Result<()> {
+            tx.execute_batch("CREATE TABLE parcial (id INTEGER); SELECT * FROM inexistente;")
+        }
+        let migracoes = [
+            Migracao {
+                versao: 1,
+                descricao: "ok",
+                aplicar: cria_tabela,
+            },
+            Migracao {
+                versao: 2,
+                descricao: "quebrada",
+                aplicar: falha_no_meio,
+            },
+        ];
+
+        let mut conn = nova_conexao();
+        let erro = aplicar_migracoes(&mut conn, &migracoes).unwrap_err();
+        assert!(matches!(erro, MigrationError::Falhou { versao: 2, .. }));
+        assert_eq!(versao_atual(&conn).unwrap(), 1);
+
+        // A tabela criada antes do erro foi desfeita junto com a transação
+        let parcial: i32 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE name = 'parcial'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(parcial, 0);
+    }
+}
@@ -18,22 +18,45 @@
 
 // Data models for MegaSena App
 
+use crate::domain::{Concurso, ContagemFaixas, Dezenas, QuantidadeConcursos};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Aposta {
     pub id: i64,
-    pub numeros: Vec<i32>,
-    pub concurso_inicial: i32,
-    pub quantidade_concursos: i32,
+    pub numeros: Dezenas,
+    pub concurso_inicial: Concurso,
+    pub quantidade_concursos: QuantidadeConcursos,
     pub data_criacao: String,
+    /// `false` quando arquivada
     pub ativa: bool,
+    /// Momento em que foi para a lixeira (`None` fora da lixeira)
+    pub excluida_em: Option<String>,
+    /// Não gera notificações
+    pub silenciada: bool,
     pub acertos: std::collections::HashMap<i32, i32>,
+    /// Apostas simples premiadas por faixa em cada concurso verificado
+    pub premiacoes: std::collections::HashMap<i32, ContagemFaixas>,
     pub resultados_concursos: std::collections::HashMap<i32, Vec<i32>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Recortes da lista de apostas
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FiltroApostas {
+    /// Não arquivadas e fora da lixeira
+    #[default]
+    Ativas,
+    Arquivadas,
+    /// Todos os concursos cobertos já foram sorteados
+    Finalizadas,
+    Lixeira,
+    /// Todas fora da lixeira
+    Todas,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Resultado {
     pub concurso: i32,
@@ -43,6 +66,52 @@ pub struct Resultado {
     pub valor_premio: Option<f64>,
     pub ganhadores: Option<i32>,
     pub valor_total: Option<f64>,
+    #[serde(default)]
+    pub faixas: Vec<FaixaPremio>,
+    /// Provedor de onde veio o resultado ("caixa", "guidi", "planilha")
+    #[serde(default)]
+    pub fonte: Option<String>,
+    /// Momento da busca (UTC, "AAAA-MM-DD HH:MM:SS")
+    #[serde(default)]
+    pub fetched_at: Option<String>,
+    /// Obtido de uma fonte não oficial; será conferido com a Caixa
+    #[serde(default)]
+    pub provisional: bool,
+    /// Corpo JSON da resposta do provedor, guardado em `resultados_raw`
+    #[serde(skip)]
+    pub payload: Option<String>,
+}
+
+#[cfg(test)]
+impl Resultado {
+    /// Resultado mínimo para testes: acumulado, sem rateio nem procedência.
+    /// Os demais campos se ajustam com `..Resultado::para_teste(..)`.
+    pub fn para_teste(concurso: i32, numeros_sorteados: Vec<i32>) -> Self {
+        Resultado {
+            concurso,
+            numeros_sorteados,
+            data_sorteio: "2023-11-01".to_string(),
+            acumulado: true,
+            valor_premio: None,
+            ganhadores: None,
+            valor_total: None,
+            faixas: vec![],
+            fonte: None,
+            fetched_at: None,
+            provisional: false,
+            payload: None,
+        }
+    }
+}
+
+/// Rateio de uma faixa de premiação (1 = Sena, 2 = Quina, 3 = Quadra)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FaixaPremio {
+    pub faixa: i32,
+    pub acertos: i32,
+    pub ganhadores: i32,
+    pub valor_premio: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -52,3 +121,183 @@ pub struct ApostaResultado {
     pub concurso: i32,
     pub acertos: i32,
 }
+
+/// Valor devido a uma aposta em uma faixa de um concurso
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PremioAposta {
+    pub aposta_id: i64,
+    pub concurso: i32,
+    pub faixa: i32,
+    pub acertos: i32,
+    /// Quantidade de apostas simples (6 números) premiadas nesta faixa
+    pub quantidade: i32,
+    pub valor_unitario: f64,
+    pub valor_total: f64,
+}
+
+/// Balanço de uma importação de resultados históricos
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumoImportacao {
+    /// Linhas ou respostas lidas, incluindo as ignoradas
+    pub total: usize,
+    pub novos: usize,
+    pub atualizados: usize,
+    /// Já estavam no banco exatamente iguais
+    pub inalterados: usize,
+    /// Linhas da planilha que não puderam ser lidas como resultado
+    pub linhas_ignoradas: usize,
+}
+
+/// Estado do disjuntor de um provedor de resultados
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum EstadoCircuito {
+    /// Provedor consultado normalmente
+    Fechado,
+    /// Provedor pulado até o fim do cooldown
+    Aberto,
+    /// Cooldown encerrado; a próxima chamada decide se ele volta
+    MeioAberto,
+}
+
+/// Saúde de um provedor, para a tela de diagnóstico
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticoProvedor {
+    pub nome: String,
+    pub prioridade: i32,
+    pub estado: EstadoCircuito,
+    pub falhas_consecutivas: u32,
+    pub total_sucessos: u64,
+    pub total_falhas: u64,
+    pub ultimo_erro: Option<String>,
+    /// Segundos até o provedor voltar a ser testado, quando aberto
+    pub reabre_em_segundos: Option<u64>,
+}
+
+/// Desfecho da comparação de um concurso entre provedores
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum StatusVerificacao {
+    /// Todos os provedores que responderam concordam
+    Concordante,
+    /// Dezenas ou prêmio da Sena diferem; o resultado não é gravado
+    Conflito,
+    /// Apenas um provedor respondeu, nada a comparar
+    FonteUnica,
+}
+
+impl StatusVerificacao {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatusVerificacao::Concordante => "concordante",
+            StatusVerificacao::Conflito => "conflito",
+            StatusVerificacao::FonteUnica => "fonteUnica",
+        }
+    }
+
+    pub fn from_db(valor: &str) -> Option<Self> {
+        match valor {
+            "concordante" => Some(StatusVerificacao::Concordante),
+            "conflito" => Some(StatusVerificacao::Conflito),
+            "fonteUnica" => Some(StatusVerificacao::FonteUnica),
+            _ => None,
+        }
+    }
+}
+
+/// Verificação cruzada de um concurso entre provedores
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificacaoResultado {
+    pub concurso: i32,
+    pub status: StatusVerificacao,
+    /// Provedores que responderam, na ordem da cadeia
+    pub provedores: Vec<String>,
+    /// Descrição de cada diferença encontrada
+    pub divergencias: Vec<String>,
+    pub verificado_em: Option<String>,
+}
+
+/// Próximo sorteio, conforme publicado junto com o último resultado
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProximoConcurso {
+    pub concurso: i32,
+    /// Data prevista do sorteio (dd/mm/aaaa)
+    pub data_sorteio: Option<String>,
+    /// Prêmio estimado para a Sena
+    pub valor_estimado: Option<f64>,
+    /// Valor acumulado que passa para o próximo concurso
+    pub valor_acumulado: Option<f64>,
+}
+
+/// Balanço da conferência dos resultados provisórios com a fonte oficial
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumoReconciliacao {
+    /// Provisórios conferidos com a fonte oficial
+    pub verificados: usize,
+    /// Conferidos sem nenhuma diferença
+    pub confirmados: usize,
+    /// Concursos em que a fonte oficial trouxe dados diferentes
+    pub corrigidos: Vec<i32>,
+    /// Ainda sem resposta da fonte oficial
+    pub pendentes: usize,
+}
+
+/// Balanço de uma sincronização dos concursos cobertos pelas apostas
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumoSincronizacao {
+    /// Último concurso sorteado, segundo os provedores ou o calendário
+    pub ultimo_concurso: i32,
+    /// Concursos que faltavam no banco
+    pub pendentes: usize,
+    /// Gravados nesta sincronização, com os acertos já processados
+    pub novos: Vec<i32>,
+    /// Provedores divergem; o resultado não foi gravado
+    pub conflitos: Vec<i32>,
+    /// Sem resposta dos provedores (ainda não publicados ou fora do ar)
+    pub indisponiveis: Vec<i32>,
+}
+
+/// Preferências das notificações do sistema
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PreferenciasNotificacao {
+    /// Menor faixa que notifica (1 = Sena, 2 = Quina, 3 = Quadra)
+    pub faixa_minima: i32,
+    /// Início do horário de silêncio, "HH:MM" no horário local
+    pub silencio_inicio: Option<String>,
+    /// Fim do horário de silêncio, "HH:MM" no horário local
+    pub silencio_fim: Option<String>,
+    /// Notifica a conferência de toda aposta, mesmo sem prêmio
+    pub notificar_sem_premio: bool,
+    /// Notifica os concursos em que a Sena acumula
+    pub notificar_acumulado: bool,
+}
+
+impl Default for PreferenciasNotificacao {
+    fn default() -> Self {
+        PreferenciasNotificacao {
+            faixa_minima: 3,
+            silencio_inicio: None,
+            silencio_fim: None,
+            notificar_sem_premio: false,
+            notificar_acumulado: false,
+        }
+    }
+}
+
+/// Corpo JSON guardado em `resultados_raw` para o resultado gravado de um concurso
+#[derive(Debug, Clone)]
+pub struct RespostaBruta {
+    pub concurso: i32,
+    pub fonte: String,
+    pub payload: String,
+    pub fetched_at: Option<String>,
+    pub provisional: bool,
+}
@@ -0,0 +1,495 @@
+/*
+ * MegaSena Monitor - Minimalist desktop application for managing bets.
+ * Copyright (C) 2025 Zander Cattapreta
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Notification rules: which system notifications a saved draw produces
+
+use crate::database::Database;
+use crate::domain::ContagemFaixas;
+use crate::error::MegaSenaError;
+use crate::models::{PreferenciasNotificacao, Resultado, VerificacaoResultado};
+use crate::premios;
+use chrono::NaiveTime;
+
+const FORMATO_HORA: &str = "%H:%M";
+
+/// Conferência de uma aposta em um concurso
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConferenciaAposta {
+    pub aposta_id: i64,
+    pub acertos: i32,
+    pub contagem: ContagemFaixas,
+    pub silenciada: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Notificacao {
+    /// Aposta premiada na faixa mínima ou acima
+    Premio {
+        aposta_id: i64,
+        concurso: i32,
+        contagem: ContagemFaixas,
+    },
+    /// Aposta conferida sem prêmio notificável (`notificar_sem_premio`)
+    Conferida {
+        aposta_id: i64,
+        concurso: i32,
+        acertos: i32,
+    },
+    /// A Sena acumulou (`notificar_acumulado`)
+    Acumulou {
+        concurso: i32,
+        valor_proximo: Option<f64>,
+    },
+    /// Provedores divergem; o resultado não foi gravado
+    Conflito(VerificacaoResultado),
+}
+
+impl Notificacao {
+    pub fn titulo(&self) -> &'static str {
+        match self {
+            Notificacao::Premio { .. } => "MegaSena Monitor - Você Ganhou! 🍀",
+            Notificacao::Conferida { .. } => "MegaSena Monitor - Aposta conferida",
+            Notificacao::Acumulou { .. } => "MegaSena Monitor - Acumulou!",
+            Notificacao::Conflito(_) => "MegaSena Monitor - Resultado em conflito",
+        }
+    }
+
+    pub fn corpo(&self) -> String {
+        match self {
+            Notificacao::Premio {
+                concurso, contagem, ..
+            } => format!(
+                "Você acertou {} no concurso {}!",
+                contagem.descricao(),
+                concurso
+            ),
+            Notificacao::Conferida {
+                concurso, acertos, ..
+            } => match acertos {
+                0 => format!("Nenhum acerto no concurso {}.", concurso),
+                1 => format!("1 acerto no concurso {}.", concurso),
+                n => format!("{} acertos no concurso {}.", n, concurso),
+            },
+            Notificacao::Acumulou {
+                concurso,
+                valor_proximo: Some(valor),
+            } => format!(
+                "Ninguém acertou a Sena no concurso {}. Próximo prêmio estimado: {}.",
+                concurso,
+                premios::formatar_moeda(*valor)
+            ),
+            Notificacao::Acumulou { concurso, .. } => {
+                format!("Ninguém acertou a Sena no concurso {}.", concurso)
+            }
+            Notificacao::Conflito(verificacao) => format!(
+                "As fontes divergem sobre o concurso {}. O resultado não foi salvo.",
+                verificacao.concurso
+            ),
+        }
+    }
+}
+
+/// Lê um horário "HH:MM"
+pub fn parse_hora(texto: &str) -> Result<NaiveTime, MegaSenaError> {
+    NaiveTime::parse_from_str(texto, FORMATO_HORA)
+        .map_err(|_| MegaSenaError::Validacao(format!("Horário inválido: '{}' (use HH:MM)", texto)))
+}
+
+/// Confere faixa mínima e horário de silêncio antes de gravar
+pub fn validar_preferencias(preferencias: &PreferenciasNotificacao) -> Result<(), MegaSenaError> {
+    if !(1..=3).contains(&preferencias.faixa_minima) {
+        return Err(MegaSenaError::Validacao(
+            "A faixa mínima deve ser 1 (Sena), 2 (Quina) ou 3 (Quadra)".to_string(),
+        ));
+    }
+    match (&preferencias.silencio_inicio, &preferencias.silencio_fim) {
+        (Some(inicio), Some(fim)) => {
+            parse_hora(inicio)?;
+            parse_hora(fim)?;
+            Ok(())
+        }
+        (None, None) => Ok(()),
+        _ => Err(MegaSenaError::Validacao(
+            "Informe o início e o fim do horário de silêncio".to_string(),
+        )),
+    }
+}
+
+/// Indica se `agora` cai no horário de silêncio. O intervalo pode virar a
+/// meia-noite (22:00 às 07:00); início igual ao fim não silencia nada.
+pub fn em_silencio(preferencias: &PreferenciasNotificacao, agora: NaiveTime) -> bool {
+    let (Some(inicio), Some(fim)) = (&preferencias.silencio_inicio, &preferencias.silencio_fim)
+    else {
+        return false;
+    };
+    let (Ok(inicio), Ok(fim)) = (parse_hora(inicio), parse_hora(fim)) else {
+        return false;
+    };
+    if inicio <= fim {
+        inicio <= agora && agora < fim
+    } else {
+        agora >= inicio || agora < fim
+    }
+}
+
+/// Aplica as regras a um concurso gravado
+pub fn avaliar(
+    preferencias: &PreferenciasNotificacao,
+    resultado: &Resultado,
+    conferencias: &[ConferenciaAposta],
+) -> Vec<Notificacao> {
+    let concurso = resultado.concurso;
+    let mut notificacoes: Vec<Notificacao> = conferencias
+        .iter()
+        .filter(|conferencia| !conferencia.silenciada)
+        .filter_map(|conferencia| {
+            let notificavel = conferencia
+                .contagem
+                .maior_faixa()
+                .is_some_and(|faixa| faixa <= preferencias.faixa_minima);
+            if notificavel {
+                Some(Notificacao::Premio {
+                    aposta_id: conferencia.aposta_id,
+                    concurso,
+                    contagem: conferencia.contagem,
+                })
+            } else if preferencias.notificar_sem_premio {
+                Some(Notificacao::Conferida {
+                    aposta_id: conferencia.aposta_id,
+                    concurso,
+                    acertos: conferencia.acertos,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    if resultado.acumulado && preferencias.notificar_acumulado {
+        // Sem ganhador, `valor_premio` é o rateio da Sena (zero) e
+        // `valor_total` guarda o acumulado ou a estimativa do próximo concurso
+        notificacoes.push(Notificacao::Acumulou {
+            concurso,
+            valor_proximo: resultado.valor_total.filter(|valor| *valor > 0.0),
+        });
+    }
+    notificacoes
+}
+
+/// Notificações dos concursos na fila e dos conflitos ainda não anunciados,
+/// marcando cada um como enviado. No horário de silêncio nada sai da fila:
+/// as notificações ficam para a próxima verificação fora dele. As marcações
+/// só valem se a lista inteira for montada; com um erro no meio, tudo
+/// continua na fila.
+pub fn entregar_pendentes(db: &Database, agora: NaiveTime) -> rusqlite::Result<Vec<Notificacao>> {
+    let preferencias = db.obter_preferencias_notificacao()?;
+    if em_silencio(&preferencias, agora) {
+        return Ok(Vec::new());
+    }
+
+    let tx = db.transacao()?;
+    let mut notificacoes = Vec::new();
+    // Um conflito segue sem resultado e volta a cada sincronização; só é
+    // anunciado uma vez
+    for verificacao in db.listar_conflitos_nao_alertados()? {
+        db.marcar_conflito_alertado(verificacao.concurso)?;
+        notificacoes.push(Notificacao::Conflito(verificacao));
+    }
+    for concurso in db.listar_notificacoes_pendentes()? {
+        if let Some(resultado) = db.obter_resultado(concurso)? {
+            let conferencias = db.listar_conferencias_concurso(concurso)?;
+            notificacoes.extend(avaliar(&preferencias, &resultado, &conferencias));
+        }
+        db.marcar_notificacao_enviada(concurso)?;
+    }
+    tx.commit()?;
+    Ok(notificacoes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Concurso, Dezenas, QuantidadeConcursos};
+    use crate::models::{FaixaPremio, StatusVerificacao};
+
+    /// Valores como os convertidos da resposta da Caixa: sem ganhador, o
+    /// rateio da Sena é zero e o total é o acumulado para o próximo concurso
+    fn resultado(concurso: i32, acumulado: bool) -> Resultado {
+        let (ganhadores, valor_premio, valor_total) = if acumulado {
+            (0, 0.0, 61_240_532.85)
+        } else {
+            (1, 118_013_691.09, 118_013_691.09)
+        };
+        Resultado {
+            data_sorteio: "31/12/2025".to_string(),
+            acumulado,
+            valor_premio: Some(valor_premio),
+            ganhadores: Some(ganhadores),
+            valor_total: Some(valor_total),
+            faixas: vec![
+                FaixaPremio {
+                    faixa: 1,
+                    acertos: 6,
+                    ganhadores,
+                    valor_premio,
+                },
+                FaixaPremio {
+                    faixa: 2,
+                    acertos: 5,
+                    ganhadores: 98,
+                    valor_premio: 41_830.19,
+                },
+                FaixaPremio {
+                    faixa: 3,
+                    acertos: 4,
+                    ganhadores: 6_902,
+                    valor_premio: 980.53,
+                },
+            ],
+            ..Resultado::para_teste(concurso, vec![1, 2, 3, 4, 5, 6])
+        }
+    }
+
+    fn conferencia(aposta_id: i64, acertos: i32, contagem: ContagemFaixas) -> ConferenciaAposta {
+        ConferenciaAposta {
+            aposta_id,
+            acertos,
+            contagem,
+            silenciada: false,
+        }
+    }
+
+    fn hora(texto: &str) -> NaiveTime {
+        parse_hora(texto).unwrap()
+    }
+
+    #[test]
+    fn test_faixa_minima_silenciadas_e_sem_premio() {
+        let quina = ContagemFaixas {
+            quinas: 1,
+            ..Default::default()
+        };
+        let quadra = ContagemFaixas {
+            quadras: 1,
+            ..Default::default()
+        };
+        let mut silenciada = conferencia(3, 5, quina);
+        silenciada.silenciada = true;
+        let conferencias = vec![
+            conferencia(1, 5, quina),
+            conferencia(2, 4, quadra),
+            silenciada,
+            conferencia(4, 0, ContagemFaixas::default()),
+        ];
+
+        // Padrão: Quadra ou acima, como antes
+        let padrao = PreferenciasNotificacao::default();
+        let notificacoes = avaliar(&padrao, &resultado(3000, false), &conferencias);
+        assert_eq!(notificacoes.len(), 2);
+        assert!(notificacoes
+            .iter()
+            .all(|n| matches!(n, Notificacao::Premio { .. })));
+
+        // Só Quina ou acima; o resto vira conferência, inclusive 0 acertos
+        let preferencias = PreferenciasNotificacao {
+            faixa_minima: 2,
+            notificar_sem_premio: true,
+            ..Default::default()
+        };
+        let notificacoes = avaliar(&preferencias, &resultado(3000, false), &conferencias);
+        assert_eq!(
+            notificacoes,
+            vec![
+                Notificacao::Premio {
+                    aposta_id: 1,
+                    concurso: 3000,
+                    contagem: quina,
+                },
+                Notificacao::Conferida {
+                    aposta_id: 2,
+                    concurso: 3000,
+                    acertos: 4,
+                },
+                Notificacao::Conferida {
+                    aposta_id: 4,
+                    concurso: 3000,
+                    acertos: 0,
+                },
+            ]
+        );
+        assert_eq!(notificacoes[2].corpo(), "Nenhum acerto no concurso 3000.");
+    }
+
+    #[test]
+    fn test_acumulado() {
+        let preferencias = PreferenciasNotificacao {
+            notificar_acumulado: true,
+            ..Default::default()
+        };
+        assert!(avaliar(&preferencias, &resultado(3000, false), &[]).is_empty());
+
+        let notificacoes = avaliar(&preferencias, &resultado(3000, true), &[]);
+        assert_eq!(
+            notificacoes,
+            vec![Notificacao::Acumulou {
+                concurso: 3000,
+                valor_proximo: Some(61_240_532.85),
+            }]
+        );
+        assert_eq!(
+            notificacoes[0].corpo(),
+            "Ninguém acertou a Sena no concurso 3000. Próximo prêmio estimado: R$ 61.240.532,85."
+        );
+        assert!(avaliar(
+            &PreferenciasNotificacao::default(),
+            &resultado(3000, true),
+            &[]
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_horario_de_silencio() {
+        let mut preferencias = PreferenciasNotificacao {
+            silencio_inicio: Some("22:00".to_string()),
+            silencio_fim: Some("07:30".to_string()),
+            ..Default::default()
+        };
+        assert!(em_silencio(&preferencias, hora("23:15")));
+        assert!(em_silencio(&preferencias, hora("03:00")));
+        assert!(!em_silencio(&preferencias, hora("07:30")));
+        assert!(!em_silencio(&preferencias, hora("20:30")));
+
+        preferencias.silencio_inicio = Some("13:00".to_string());
+        preferencias.silencio_fim = Some("14:00".to_string());
+        assert!(em_silencio(&preferencias, hora("13:30")));
+        assert!(!em_silencio(&preferencias, hora("14:30")));
+
+        assert!(!em_silencio(
+            &PreferenciasNotificacao::default(),
+            hora("03:00")
+        ));
+        preferencias.silencio_fim = None;
+        assert!(validar_preferencias(&preferencias).is_err());
+        preferencias.silencio_fim = Some("25:00".to_string());
+        assert!(validar_preferencias(&preferencias).is_err());
+    }
+
+    #[test]
+    fn test_entrega_adiada_e_sem_repeticao() {
+        let mut db = Database::new(":memory:".into()).unwrap();
+        db.init().unwrap();
+        db.adicionar_aposta(
+            &Dezenas::novas(vec![1, 2, 3, 4, 10, 20]).unwrap(),
+            Concurso::novo(3000).unwrap(),
+            QuantidadeConcursos::nova(1).unwrap(),
+        )
+        .unwrap();
+        db.salvar_resultado(&resultado(3000, false)).unwrap();
+        db.processar_acertos_concurso(3000).unwrap();
+        db.definir_preferencias_notificacao(&PreferenciasNotificacao {
+            silencio_inicio: Some("22:00".to_string()),
+            silencio_fim: Some("07:00".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        db.enfileirar_notificacao(3000).unwrap();
+
+        // No silêncio, o concurso continua na fila
+        assert!(entregar_pendentes(&db, hora("23:00")).unwrap().is_empty());
+        assert_eq!(db.listar_notificacoes_pendentes().unwrap(), vec![3000]);
+
+        let notificacoes = entregar_pendentes(&db, hora("08:00")).unwrap();
+        assert_eq!(notificacoes.len(), 1);
+        assert_eq!(
+            notificacoes[0].corpo(),
+            "Você acertou 1 Quadra no concurso 3000!"
+        );
+
+        // Nem uma nova sincronização nem um reinício notificam de novo
+        db.enfileirar_notificacao(3000).unwrap();
+        assert!(entregar_pendentes(&db, hora("09:00")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_conflito_alertado_uma_vez() {
+        let mut db = Database::new(":memory:".into()).unwrap();
+        db.init().unwrap();
+        let mut verificacao = VerificacaoResultado {
+            concurso: 3000,
+            status: StatusVerificacao::Conflito,
+            provedores: vec!["caixa".to_string(), "guidi".to_string()],
+            divergencias: vec!["dezenas diferem".to_string()],
+            verificado_em: None,
+        };
+        db.registrar_verificacao(&verificacao).unwrap();
+
+        let notificacoes = entregar_pendentes(&db, hora("21:00")).unwrap();
+        assert_eq!(notificacoes.len(), 1);
+        assert!(matches!(&notificacoes[0], Notificacao::Conflito(v) if v.concurso == 3000));
+
+        // A sincronização seguinte encontra o mesmo conflito: sem novo alerta
+        db.registrar_verificacao(&verificacao).unwrap();
+        assert!(entregar_pendentes(&db, hora("21:10")).unwrap().is_empty());
+
+        // Resolvido e depois em conflito de novo: alerta outra vez
+        verificacao.status = StatusVerificacao::Concordante;
+        db.registrar_verificacao(&verificacao).unwrap();
+        verificacao.status = StatusVerificacao::Conflito;
+        db.registrar_verificacao(&verificacao).unwrap();
+        assert_eq!(entregar_pendentes(&db, hora("21:20")).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_erro_no_meio_mantem_a_fila() {
+        let caminho =
+            std::env::temp_dir().join(format!("megasena-notificacoes-{}.db", std::process::id()));
+        let mut db = Database::new(caminho.clone()).unwrap();
+        db.init().unwrap();
+        db.registrar_verificacao(&VerificacaoResultado {
+            concurso: 2999,
+            status: StatusVerificacao::Conflito,
+            provedores: vec!["caixa".to_string(), "guidi".to_string()],
+            divergencias: vec!["dezenas diferem".to_string()],
+            verificado_em: None,
+        })
+        .unwrap();
+        for concurso in [3000, 3001] {
+            db.salvar_resultado(&resultado(concurso, true)).unwrap();
+            db.enfileirar_notificacao(concurso).unwrap();
+        }
+        // O segundo concurso não pode ser lido
+        rusqlite::Connection::open(&caminho)
+            .unwrap()
+            .execute(
+                "UPDATE resultados SET valor_premio = 'corrompido' WHERE concurso = 3001",
+                [],
+            )
+            .unwrap();
+
+        assert!(entregar_pendentes(&db, hora("21:00")).is_err());
+        assert_eq!(
+            db.listar_notificacoes_pendentes().unwrap(),
+            vec![3000, 3001]
+        );
+        assert_eq!(db.listar_conflitos_nao_alertados().unwrap().len(), 1);
+
+        drop(db);
+        std::fs::remove_file(&caminho).unwrap();
+    }
+}
@@ -0,0 +1,144 @@
+/*
+ * MegaSena Monitor - Minimalist desktop application for managing bets.
+ * Copyright (C) 2025 Zander Cattapreta
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Prize calculation: turns hits into money using the contest's tier payouts
+
+use crate::domain::ContagemFaixas;
+use crate::models::{FaixaPremio, PremioAposta};
+
+/// Calcula o prêmio de uma aposta em um concurso, faixa a faixa, a partir da
+/// contagem de apostas simples premiadas. Faixas sem premiação não são retornadas.
+pub fn calcular_premios(
+    aposta_id: i64,
+    concurso: i32,
+    contagem: &ContagemFaixas,
+    faixas: &[FaixaPremio],
+) -> Vec<PremioAposta> {
+    faixas
+        .iter()
+        .filter_map(|faixa| {
+            let quantidade = contagem.quantidade(faixa.acertos);
+            if quantidade == 0 {
+                return None;
+            }
+            Some(PremioAposta {
+                aposta_id,
+                concurso,
+                faixa: faixa.faixa,
+                acertos: faixa.acertos,
+                quantidade,
+                valor_unitario: faixa.valor_premio,
+                valor_total: quantidade as f64 * faixa.valor_premio,
+            })
+        })
+        .collect()
+}
+
+/// Valor em reais no formato brasileiro: "R$ 3.500.000,00"
+pub fn formatar_moeda(valor: f64) -> String {
+    let centavos = (valor.abs() * 100.0).round() as u64;
+    let inteiro = (centavos / 100).to_string();
+
+    let mut milhares = String::new();
+    for (i, c) in inteiro.chars().enumerate() {
+        if i > 0 && (inteiro.len() - i).is_multiple_of(3) {
+            milhares.push('.');
+        }
+        milhares.push(c);
+    }
+
+    let sinal = if valor < 0.0 { "-" } else { "" };
+    format!("{}R$ {},{:02}", sinal, milhares, centavos % 100)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::expandir_aposta;
+
+    fn faixas_exemplo() -> Vec<FaixaPremio> {
+        vec![
+            FaixaPremio {
+                faixa: 1,
+                acertos: 6,
+                ganhadores: 1,
+                valor_premio: 50_000_000.0,
+            },
+            FaixaPremio {
+                faixa: 2,
+                acertos: 5,
+                ganhadores: 100,
+                valor_premio: 40_000.0,
+            },
+            FaixaPremio {
+                faixa: 3,
+                acertos: 4,
+                ganhadores: 5_000,
+                valor_premio: 1_000.0,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_aposta_simples_quadra() {
+        let contagem = expandir_aposta(&[1, 2, 3, 4, 5, 6], &[1, 2, 3, 4, 50, 60]);
+        let premios = calcular_premios(1, 2700, &contagem, &faixas_exemplo());
+        assert_eq!(premios.len(), 1);
+        assert_eq!(premios[0].acertos, 4);
+        assert_eq!(premios[0].quantidade, 1);
+        assert_eq!(premios[0].valor_total, 1_000.0);
+    }
+
+    #[test]
+    fn test_aposta_sete_numeros_sena() {
+        // 7 números com 6 acertos: 1 Sena + 6 Quinas
+        let contagem = expandir_aposta(&[1, 2, 3, 4, 5, 6, 7], &[1, 2, 3, 4, 5, 6]);
+        let premios = calcular_premios(1, 2700, &contagem, &faixas_exemplo());
+        assert_eq!(premios.len(), 2);
+        assert_eq!(premios[0].quantidade, 1);
+        assert_eq!(premios[1].quantidade, 6);
+        assert_eq!(premios[1].valor_total, 240_000.0);
+    }
+
+    #[test]
+    fn test_aposta_dez_numeros_quina() {
+        // 10 números com 5 acertos: 5 Quinas + 50 Quadras
+        let aposta: Vec<i32> = (1..=10).collect();
+        let contagem = expandir_aposta(&aposta, &[1, 2, 3, 4, 5, 60]);
+        let premios = calcular_premios(1, 2700, &contagem, &faixas_exemplo());
+        let total: f64 = premios.iter().map(|p| p.valor_total).sum();
+        assert_eq!(premios[0].quantidade, 5);
+        assert_eq!(premios[1].quantidade, 50);
+        assert_eq!(total, 5.0 * 40_000.0 + 50.0 * 1_000.0);
+    }
+
+    #[test]
+    fn test_sem_premio() {
+        let aposta: Vec<i32> = (1..=15).collect();
+        let contagem = expandir_aposta(&aposta, &[1, 2, 3, 40, 50, 60]);
+        assert!(calcular_premios(1, 2700, &contagem, &faixas_exemplo()).is_empty());
+    }
+
+    #[test]
+    fn test_formatar_moeda() {
+        assert_eq!(formatar_moeda(3_500_000.0), "R$ 3.500.000,00");
+        assert_eq!(formatar_moeda(419.51), "R$ 419,51");
+        assert_eq!(formatar_moeda(1_234.5), "R$ 1.234,50");
+        assert_eq!(formatar_moeda(0.0), "R$ 0,00");
+    }
+}
@@ -0,0 +1,216 @@
+/*
+ * MegaSena Monitor - Minimalist desktop application for managing bets.
+ * Copyright (C) 2025 Zander Cattapreta
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Re-check of provisional results (third-party fallbacks) against Caixa
+
+use crate::api::ProviderChain;
+use crate::database::Database;
+use crate::db_actor::DbActor;
+use crate::error::MegaSenaError;
+use crate::eventos::Evento;
+use crate::models::{Resultado, ResumoReconciliacao};
+use crate::sincronizacao;
+
+/// Grava o resultado oficial no lugar do provisório e devolve as diferenças.
+/// Se as dezenas mudaram, os acertos foram recalculados: os eventos do novo
+/// resultado são devolvidos e o concurso volta à fila de notificações, já
+/// que a notificação anterior foi feita com as dezenas erradas.
+fn gravar_oficial(
+    db: &Database,
+    oficial: &Resultado,
+) -> rusqlite::Result<(Vec<String>, Vec<Evento>)> {
+    let mut anteriores = db.obter_dezenas_resultado(oficial.concurso)?;
+    let diferencas = db.reconciliar_resultado(oficial)?;
+
+    let mut oficiais = oficial.numeros_sorteados.clone();
+    anteriores.sort_unstable();
+    oficiais.sort_unstable();
+    if anteriores == oficiais {
+        return Ok((diferencas, Vec::new()));
+    }
+    db.reenfileirar_notificacao(oficial.concurso)?;
+    let eventos = sincronizacao::eventos_do_resultado(db, oficial)?;
+    Ok((diferencas, eventos))
+}
+
+/// Confere com a fonte oficial os resultados gravados a partir de fontes
+/// alternativas. O banco só é usado para ler a lista e gravar cada
+/// resultado, nunca durante as requisições. As correções são informadas
+/// por `emitir`.
+pub async fn reconciliar_provisorios(
+    db: &DbActor,
+    providers: &ProviderChain,
+    emitir: &(dyn Fn(Evento) + Sync),
+) -> Result<ResumoReconciliacao, MegaSenaError> {
+    let provisorios = db
+        .executar_async(|db| db.listar_resultados_provisorios())
+        .await??;
+
+    let mut resumo = ResumoReconciliacao::default();
+    for concurso in provisorios {
+        let chain = providers.clone();
+        let buscado = tokio::task::spawn_blocking(move || chain.buscar_oficial(concurso))
+            .await
+            .map_err(|e| MegaSenaError::Rede(e.to_string()))?;
+        let oficial = match buscado {
+            Ok(Some(oficial)) => oficial,
+            Ok(None) => {
+                resumo.pendentes += 1;
+                continue;
+            }
+            Err(e) => {
+                eprintln!("Concurso {} ainda não conferido: {}", concurso, e);
+                resumo.pendentes += 1;
+                continue;
+            }
+        };
+
+        let (diferencas, eventos) = db
+            .executar_async(move |db| gravar_oficial(db, &oficial))
+            .await??;
+        eventos.into_iter().for_each(emitir);
+
+        resumo.verificados += 1;
+        if diferencas.is_empty() {
+            resumo.confirmados += 1;
+        } else {
+            resumo.corrigidos.push(concurso);
+        }
+    }
+
+    Ok(resumo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{ErroBusca, ResultProvider};
+    use crate::domain::{Concurso, Dezenas, QuantidadeConcursos};
+    use crate::eventos::ApostaPremiada;
+    use std::sync::Mutex;
+
+    /// Fonte oficial que publica `dezenas` para qualquer concurso até `ultimo`
+    struct Oficial {
+        ultimo: i32,
+        dezenas: Vec<i32>,
+    }
+
+    impl ResultProvider for Oficial {
+        fn name(&self) -> &str {
+            "caixa"
+        }
+
+        fn priority(&self) -> i32 {
+            0
+        }
+
+        fn fetch(&self, concurso: i32) -> Result<Resultado, ErroBusca> {
+            if concurso > self.ultimo {
+                return Err(ErroBusca::NaoEncontrado);
+            }
+            Ok(resultado(concurso, self.dezenas.clone(), false))
+        }
+
+        fn latest(&self) -> Result<Resultado, ErroBusca> {
+            self.fetch(self.ultimo)
+        }
+
+        fn official(&self) -> bool {
+            true
+        }
+    }
+
+    fn resultado(concurso: i32, numeros_sorteados: Vec<i32>, provisional: bool) -> Resultado {
+        Resultado {
+            data_sorteio: "01/01/2024".to_string(),
+            fonte: Some("guidi".to_string()),
+            provisional,
+            ..Resultado::para_teste(concurso, numeros_sorteados)
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reconciliar_provisorios() {
+        let mut db = Database::new(":memory:".into()).unwrap();
+        db.init().unwrap();
+        let aposta = db
+            .adicionar_aposta(
+                &Dezenas::novas(vec![1, 2, 3, 4, 5, 6]).unwrap(),
+                Concurso::novo(2700).unwrap(),
+                QuantidadeConcursos::nova(3).unwrap(),
+            )
+            .unwrap();
+        for (concurso, dezenas) in [
+            (2700, vec![1, 2, 3, 4, 5, 6]),
+            (2701, vec![1, 2, 3, 50, 51, 52]),
+            (2702, vec![10, 20, 30, 40, 50, 60]),
+        ] {
+            db.salvar_resultado(&resultado(concurso, dezenas, true))
+                .unwrap();
+            db.processar_acertos_concurso(concurso).unwrap();
+            // Notificados com as dezenas provisórias
+            db.enfileirar_notificacao(concurso).unwrap();
+            db.marcar_notificacao_enviada(concurso).unwrap();
+        }
+        let db = DbActor::iniciar(db);
+
+        // A Caixa confirma 2700, corrige 2701 e ainda não publicou 2702
+        let oficial = Oficial {
+            ultimo: 2701,
+            dezenas: vec![1, 2, 3, 4, 5, 6],
+        };
+        let providers = ProviderChain::new(vec![Box::new(oficial)]);
+        let emitidos = Mutex::new(Vec::new());
+        let resumo = reconciliar_provisorios(&db, &providers, &|evento| {
+            emitidos.lock().unwrap().push(evento)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(resumo.verificados, 2);
+        assert_eq!(resumo.confirmados, 1);
+        assert_eq!(resumo.corrigidos, vec![2701]);
+        assert_eq!(resumo.pendentes, 1);
+
+        // Só a correção de dezenas gera eventos e volta à fila de notificações
+        let emitidos = emitidos.into_inner().unwrap();
+        let nomes: Vec<_> = emitidos.iter().map(Evento::nome).collect();
+        assert_eq!(nomes, vec!["resultado-salvo", "aposta-premiada"]);
+        assert!(emitidos.contains(&Evento::ApostaPremiada(ApostaPremiada {
+            aposta_id: aposta.id,
+            concurso: 2701,
+            faixa: 1,
+        })));
+
+        let (provisorios, acertos, fonte, notificar) = db
+            .executar_async(move |db| {
+                (
+                    db.listar_resultados_provisorios().unwrap(),
+                    db.obter_acertos_aposta(aposta.id).unwrap(),
+                    db.obter_resultado(2701).unwrap().unwrap().fonte,
+                    db.listar_notificacoes_pendentes().unwrap(),
+                )
+            })
+            .await
+            .unwrap();
+        assert_eq!(notificar, vec![2701]);
+        assert_eq!(provisorios, vec![2702]);
+        assert_eq!(acertos.get(&2701), Some(&6));
+        assert_eq!(fonte.as_deref(), Some("caixa"));
+    }
+}
@@ -0,0 +1,216 @@
+/*
+ * MegaSena Monitor - Minimalist desktop application for managing bets.
+ * Copyright (C) 2025 Zander Cattapreta
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Retry backoff and circuit breaker for result providers
+
+use crate::config::{CircuitBreakerConfig, RetryConfig};
+use crate::models::EstadoCircuito;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Espera antes da tentativa `tentativa` (a primeira repetição é a 1):
+/// metade do teto exponencial garantida e a outra metade aleatória, para que
+/// requisições paralelas não voltem todas ao mesmo tempo.
+pub fn atraso_backoff(config: &RetryConfig, tentativa: u32) -> Duration {
+    let teto = config
+        .base_delay_ms
+        .saturating_mul(1u64 << tentativa.saturating_sub(1).min(20))
+        .min(config.max_delay_ms);
+    let metade = teto / 2;
+    let aleatorio = RandomState::new().hash_one((Instant::now(), tentativa));
+    Duration::from_millis(metade + aleatorio % (teto - metade + 1))
+}
+
+#[derive(Debug, Default)]
+struct Estado {
+    falhas_consecutivas: u32,
+    /// Momento em que o circuito abriu (`None` = fechado)
+    aberto_desde: Option<Instant>,
+    /// Chamada de teste liberada após o cooldown ainda sem resposta
+    teste_em_andamento: bool,
+    total_sucessos: u64,
+    total_falhas: u64,
+    ultimo_erro: Option<String>,
+}
+
+/// Fotografia do disjuntor para diagnóstico
+#[derive(Debug, Clone)]
+pub struct SituacaoCircuito {
+    pub estado: EstadoCircuito,
+    pub falhas_consecutivas: u32,
+    pub total_sucessos: u64,
+    pub total_falhas: u64,
+    pub ultimo_erro: Option<String>,
+    /// Tempo até liberar a próxima chamada de teste, quando aberto
+    pub reabre_em: Option<Duration>,
+}
+
+/// Disjuntor por provedor: após `failure_threshold` falhas seguidas o
+/// provedor é pulado por `cooldown_secs`; depois disso uma única chamada de
+/// teste decide se ele volta (sucesso) ou fica mais um período fora (falha).
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    estado: Mutex<Estado>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        CircuitBreaker {
+            config,
+            estado: Mutex::new(Estado::default()),
+        }
+    }
+
+    fn cooldown(&self) -> Duration {
+        Duration::from_secs(self.config.cooldown_secs)
+    }
+
+    fn estado(&self) -> std::sync::MutexGuard<'_, Estado> {
+        // O estado continua consistente mesmo se outra thread entrou em pânico
+        self.estado.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Indica se o provedor pode ser chamado agora
+    pub fn permitir(&self) -> bool {
+        let mut estado = self.estado();
+        match estado.aberto_desde {
+            None => true,
+            Some(desde) if desde.elapsed() >= self.cooldown() && !estado.teste_em_andamento => {
+                estado.teste_em_andamento = true;
+                true
+            }
+            Some(_) => false,
+        }
+    }
+
+    pub fn registrar_sucesso(&self) {
+        let mut estado = self.estado();
+        estado.falhas_consecutivas = 0;
+        estado.aberto_desde = None;
+        estado.teste_em_andamento = false;
+        estado.total_sucessos += 1;
+    }
+
+    pub fn registrar_falha(&self, erro: &str) {
+        let mut estado = self.estado();
+        estado.falhas_consecutivas += 1;
+        estado.total_falhas += 1;
+        estado.ultimo_erro = Some(erro.to_string());
+
+        if estado.teste_em_andamento {
+            estado.teste_em_andamento = false;
+            estado.aberto_desde = Some(Instant::now());
+        } else if estado.aberto_desde.is_none()
+            && estado.falhas_consecutivas >= self.config.failure_threshold
+        {
+            estado.aberto_desde = Some(Instant::now());
+        }
+    }
+
+    pub fn situacao(&self) -> SituacaoCircuito {
+        let estado = self.estado();
+        let restante = estado
+            .aberto_desde
+            .map(|desde| self.cooldown().saturating_sub(desde.elapsed()));
+
+        SituacaoCircuito {
+            estado: match restante {
+                None => EstadoCircuito::Fechado,
+                Some(r) if r.is_zero() => EstadoCircuito::MeioAberto,
+                Some(_) => EstadoCircuito::Aberto,
+            },
+            falhas_consecutivas: estado.falhas_consecutivas,
+            total_sucessos: estado.total_sucessos,
+            total_falhas: estado.total_falhas,
+            ultimo_erro: estado.ultimo_erro.clone(),
+            reabre_em: restante.filter(|r| !r.is_zero()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(failure_threshold: u32, cooldown_secs: u64) -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold,
+            cooldown_secs,
+        })
+    }
+
+    #[test]
+    fn test_backoff_cresce_com_teto() {
+        let config = RetryConfig {
+            max_attempts: 5,
+            base_delay_ms: 100,
+            max_delay_ms: 1_000,
+        };
+        for _ in 0..20 {
+            let primeira = atraso_backoff(&config, 1).as_millis();
+            assert!((50..=100).contains(&primeira), "{}", primeira);
+            let terceira = atraso_backoff(&config, 3).as_millis();
+            assert!((200..=400).contains(&terceira), "{}", terceira);
+            let decima = atraso_backoff(&config, 10).as_millis();
+            assert!((500..=1_000).contains(&decima), "{}", decima);
+        }
+    }
+
+    #[test]
+    fn test_circuito_abre_apos_limite() {
+        let cb = breaker(3, 60);
+        cb.registrar_falha("500");
+        cb.registrar_falha("500");
+        assert!(cb.permitir());
+        cb.registrar_falha("timeout");
+
+        assert!(!cb.permitir());
+        let situacao = cb.situacao();
+        assert_eq!(situacao.estado, EstadoCircuito::Aberto);
+        assert_eq!(situacao.ultimo_erro.as_deref(), Some("timeout"));
+        assert!(situacao.reabre_em.is_some());
+    }
+
+    #[test]
+    fn test_meio_aberto_libera_um_teste() {
+        let cb = breaker(1, 0);
+        cb.registrar_falha("500");
+        assert_eq!(cb.situacao().estado, EstadoCircuito::MeioAberto);
+
+        assert!(cb.permitir());
+        assert!(!cb.permitir(), "apenas uma chamada de teste por vez");
+
+        cb.registrar_sucesso();
+        assert_eq!(cb.situacao().estado, EstadoCircuito::Fechado);
+        assert!(cb.permitir());
+    }
+
+    #[test]
+    fn test_teste_falho_reabre() {
+        let cb = breaker(1, 0);
+        cb.registrar_falha("500");
+        assert!(cb.permitir());
+        cb.registrar_falha("500 de novo");
+        assert_eq!(cb.situacao().total_falhas, 2);
+        // Cooldown zero: novo teste liberado de imediato
+        assert!(cb.permitir());
+    }
+}
@@ -0,0 +1,331 @@
+/*
+ * MegaSena Monitor - Minimalist desktop application for managing bets.
+ * Copyright (C) 2025 Zander Cattapreta
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Catch-up sync: fetch every drawn contest still missing for the bets
+
+use crate::api::{ProviderChain, ResultadoBuscado};
+use crate::database::Database;
+use crate::db_actor::DbActor;
+use crate::error::MegaSenaError;
+use crate::eventos::{ApostaPremiada, Evento, InicioSincronizacao, ProgressoSincronizacao};
+use crate::models::{Resultado, ResumoSincronizacao};
+
+/// Grava um resultado verificado e processa os acertos. Devolve os eventos
+/// decorrentes (`resultado-salvo` e um `aposta-premiada` por aposta
+/// premiada), ou `None` se os provedores divergem e nada foi gravado.
+pub fn gravar_buscado(
+    db: &Database,
+    buscado: &ResultadoBuscado,
+) -> rusqlite::Result<Option<Vec<Evento>>> {
+    let concurso = buscado.resultado.concurso;
+    if !db.salvar_resultado_verificado(&buscado.resultado, buscado.verificacao.as_ref())? {
+        return Ok(None);
+    }
+    db.processar_acertos_concurso(concurso)?;
+    eventos_do_resultado(db, &buscado.resultado).map(Some)
+}
+
+/// Eventos de um resultado recém-gravado, com os acertos já processados
+pub fn eventos_do_resultado(db: &Database, resultado: &Resultado) -> rusqlite::Result<Vec<Evento>> {
+    let concurso = resultado.concurso;
+    let mut eventos = vec![Evento::ResultadoSalvo(resultado.clone())];
+    for (aposta_id, contagem) in db.listar_premiacoes_concurso(concurso)? {
+        if let Some(faixa) = contagem.maior_faixa() {
+            eventos.push(Evento::ApostaPremiada(ApostaPremiada {
+                aposta_id,
+                concurso,
+                faixa,
+            }));
+        }
+    }
+    Ok(eventos)
+}
+
+/// Busca o último concurso e todos os concursos já sorteados do período das
+/// apostas que ainda faltam no banco, gravando cada um e processando os
+/// acertos. As requisições correm em paralelo, fora da thread do banco.
+/// Cada concurso gravado entra na fila de notificações. O andamento e o que
+/// foi gravado são informados por `emitir`.
+pub async fn sincronizar(
+    db: &DbActor,
+    providers: &ProviderChain,
+    emitir: &(dyn Fn(Evento) + Sync),
+) -> Result<ResumoSincronizacao, MegaSenaError> {
+    let cache = db
+        .executar_async(|db| db.obter_ultimo_resultado())
+        .await??;
+    let chain = providers.clone();
+    let ultimo_concurso =
+        tokio::task::spawn_blocking(move || chain.obter_ultimo_concurso_numero(cache.as_ref()))
+            .await
+            .map_err(|e| MegaSenaError::Rede(e.to_string()))??;
+
+    let pendentes = db
+        .executar_async(move |db| -> rusqlite::Result<Vec<i32>> {
+            let mut pendentes = db.listar_concursos_pendentes(ultimo_concurso)?;
+            if pendentes.last() != Some(&ultimo_concurso)
+                && db.obter_resultado(ultimo_concurso)?.is_none()
+            {
+                pendentes.push(ultimo_concurso);
+            }
+            Ok(pendentes)
+        })
+        .await??;
+
+    let mut resumo = ResumoSincronizacao {
+        ultimo_concurso,
+        pendentes: pendentes.len(),
+        ..Default::default()
+    };
+    let total = pendentes.len();
+    emitir(Evento::SincronizacaoIniciada(InicioSincronizacao { total }));
+    if pendentes.is_empty() {
+        return Ok(resumo);
+    }
+    println!(
+        "Sincronizando {} concurso(s) pendente(s) até {}",
+        pendentes.len(),
+        ultimo_concurso
+    );
+
+    for (done, (concurso, busca)) in providers.iniciar_buscas(&pendentes).into_iter().enumerate() {
+        match busca.concluir().await {
+            Ok(buscado) => {
+                let eventos = db
+                    .executar_async(move |db| -> rusqlite::Result<_> {
+                        let eventos = gravar_buscado(db, &buscado)?;
+                        if eventos.is_some() {
+                            db.enfileirar_notificacao(concurso)?;
+                        }
+                        Ok(eventos)
+                    })
+                    .await??;
+                match eventos {
+                    Some(eventos) => {
+                        resumo.novos.push(concurso);
+                        eventos.into_iter().for_each(emitir);
+                    }
+                    None => resumo.conflitos.push(concurso),
+                }
+            }
+            Err(e) => {
+                eprintln!("Concurso {} indisponível: {}", concurso, e);
+                resumo.indisponiveis.push(concurso);
+            }
+        }
+        emitir(Evento::ProgressoSincronizacao(ProgressoSincronizacao {
+            done: done + 1,
+            total,
+            concurso,
+        }));
+    }
+
+    Ok(resumo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{ErroBusca, ResultProvider};
+    use crate::database::Database;
+    use crate::domain::{Concurso, Dezenas, QuantidadeConcursos};
+    use std::sync::Mutex;
+    use std::time::{Duration, Instant};
+
+    /// Publica as dezenas 1 a 6 para todos os concursos até `ultimo`,
+    /// demorando `atraso` em cada resposta
+    struct Publicados {
+        ultimo: i32,
+        atraso: Duration,
+    }
+
+    impl ResultProvider for Publicados {
+        fn name(&self) -> &str {
+            "caixa"
+        }
+
+        fn priority(&self) -> i32 {
+            0
+        }
+
+        fn fetch(&self, concurso: i32) -> Result<Resultado, ErroBusca> {
+            std::thread::sleep(self.atraso);
+            if concurso > self.ultimo {
+                return Err(ErroBusca::NaoEncontrado);
+            }
+            Ok(Resultado {
+                data_sorteio: "31/12/2025".to_string(),
+                acumulado: false,
+                ..Resultado::para_teste(concurso, vec![1, 2, 3, 4, 5, 6])
+            })
+        }
+
+        fn latest(&self) -> Result<Resultado, ErroBusca> {
+            self.fetch(self.ultimo)
+        }
+
+        fn official(&self) -> bool {
+            true
+        }
+    }
+
+    fn apostar(db: &Database, concurso: i32, quantidade: i32) -> i64 {
+        db.adicionar_aposta(
+            &Dezenas::novas(vec![1, 2, 3, 4, 5, 6]).unwrap(),
+            Concurso::novo(concurso).unwrap(),
+            QuantidadeConcursos::nova(quantidade).unwrap(),
+        )
+        .unwrap()
+        .id
+    }
+
+    #[test]
+    fn test_concursos_pendentes() {
+        let mut db = Database::new(":memory:".into()).unwrap();
+        db.init().unwrap();
+        apostar(&db, 2700, 3);
+        apostar(&db, 2701, 4);
+        let excluida = apostar(&db, 2690, 2);
+        db.mover_para_lixeira(excluida).unwrap();
+        db.salvar_resultado(
+            &Publicados {
+                ultimo: 2800,
+                atraso: Duration::ZERO,
+            }
+            .fetch(2702)
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            db.listar_concursos_pendentes(2703).unwrap(),
+            vec![2700, 2701, 2703]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_sincronizar_recupera_concursos_perdidos() {
+        let mut db = Database::new(":memory:".into()).unwrap();
+        db.init().unwrap();
+        let aposta = apostar(&db, 3000, 12);
+        let db = DbActor::iniciar(db);
+
+        // O app ficou desligado: 3000 a 3005 já foram sorteados
+        let providers = ProviderChain::new(vec![Box::new(Publicados {
+            ultimo: 3005,
+            atraso: Duration::ZERO,
+        })]);
+        let resumo = sincronizar(&db, &providers, &|_| {}).await.unwrap();
+
+        assert_eq!(resumo.ultimo_concurso, 3005);
+        assert_eq!(resumo.pendentes, 6);
+        assert_eq!(resumo.novos, (3000..=3005).collect::<Vec<_>>());
+        assert!(resumo.conflitos.is_empty() && resumo.indisponiveis.is_empty());
+
+        let acertos = db
+            .executar_async(move |db| db.obter_acertos_aposta(aposta))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(acertos.len(), 6);
+        assert!(acertos.values().all(|&acertos| acertos == 6));
+
+        // Nada mais a buscar
+        let resumo = sincronizar(&db, &providers, &|_| {}).await.unwrap();
+        assert_eq!(resumo.pendentes, 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_sincronizar_emite_eventos() {
+        let mut db = Database::new(":memory:".into()).unwrap();
+        db.init().unwrap();
+        let aposta = apostar(&db, 3000, 2);
+        let db = DbActor::iniciar(db);
+        let providers = ProviderChain::new(vec![Box::new(Publicados {
+            ultimo: 3001,
+            atraso: Duration::ZERO,
+        })]);
+
+        let emitidos = Mutex::new(Vec::new());
+        sincronizar(&db, &providers, &|evento| {
+            emitidos.lock().unwrap().push(evento)
+        })
+        .await
+        .unwrap();
+
+        let emitidos = emitidos.into_inner().unwrap();
+        let nomes: Vec<_> = emitidos.iter().map(Evento::nome).collect();
+        assert_eq!(
+            nomes,
+            vec![
+                "sync-started",
+                "resultado-salvo",
+                "aposta-premiada",
+                "sync-progress",
+                "resultado-salvo",
+                "aposta-premiada",
+                "sync-progress",
+            ]
+        );
+        assert!(emitidos.contains(&Evento::ApostaPremiada(ApostaPremiada {
+            aposta_id: aposta,
+            concurso: 3001,
+            faixa: 1,
+        })));
+        assert_eq!(
+            emitidos.last(),
+            Some(&Evento::ProgressoSincronizacao(ProgressoSincronizacao {
+                done: 2,
+                total: 2,
+                concurso: 3001,
+            }))
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_banco_livre_durante_busca_lenta() {
+        let mut db = Database::new(":memory:".into()).unwrap();
+        db.init().unwrap();
+        apostar(&db, 3000, 3);
+        let db = DbActor::iniciar(db);
+        let providers = ProviderChain::new(vec![Box::new(Publicados {
+            ultimo: 3002,
+            atraso: Duration::from_millis(400),
+        })]);
+
+        let sincronizando = {
+            let (db, providers) = (db.clone(), providers.clone());
+            tokio::spawn(async move { sincronizar(&db, &providers, &|_| {}).await })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // Com o provedor ainda respondendo, o banco atende na hora
+        let inicio = Instant::now();
+        let apostas = db
+            .executar_async(|db| db.listar_apostas())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(apostas.len(), 1);
+        assert!(inicio.elapsed() < Duration::from_millis(200));
+        assert!(!sincronizando.is_finished());
+
+        let resumo = sincronizando.await.unwrap().unwrap();
+        assert_eq!(resumo.novos, vec![3000, 3001, 3002]);
+    }
+}
@@ -16,7 +16,12 @@ import { listen } from "@tauri-apps/api/event";
 import { SettingsService } from "./services/settings";
 import appIcon from "./assets/app-icon.png";
 import * as tauri from "./services/tauri";
-import { Aposta, Resultado } from "./types";
+import {
+  Aposta,
+  InicioSincronizacao,
+  ProgressoSincronizacao,
+  Resultado,
+} from "./types";
 import "./App.css";
 
 import { listen as tauriListen } from "@tauri-apps/api/event";
@@ -99,14 +104,41 @@ function App() {
       }
 
       let verificadas = results.filter((r) => r.status === "fulfilled").length;
-      let erros = results.length - verificadas;
+      const falhas = results.flatMap((r) =>
+        r.status === "rejected" ? [r.reason] : [],
+      );
+      const naoSorteados = falhas.filter(
+        (e) => tauri.ehMegaSenaError(e) && e.code === "notDrawn",
+      ).length;
+      const semConexao = falhas.filter(
+        (e) => tauri.ehMegaSenaError(e) && e.code === "network",
+      ).length;
+      const outros = falhas.length - naoSorteados - semConexao;
 
       toast.dismiss("verificando");
       if (verificadas > 0)
         toast.success(`${verificadas} concurso(s) conferido(s)!`, {
           icon: "🎉",
         });
-      if (erros > 0) toast.error(`${erros} concurso(s) indisponíveis.`);
+      if (naoSorteados > 0)
+        toast(`${naoSorteados} concurso(s) ainda não sorteado(s).`, {
+          icon: "⌛",
+        });
+      if (semConexao > 0)
+        toast.error(
+          `${semConexao} concurso(s) sem resposta. Verifique sua conexão.`,
+          { icon: "📡" },
+        );
+      if (outros > 0) {
+        const primeira = falhas.find(
+          (e) =>
+            !tauri.ehMegaSenaError(e) ||
+            (e.code !== "notDrawn" && e.code !== "network"),
+        );
+        toast.error(
+          `${outros} concurso(s) indisponíveis: ${tauri.mensagemErro(primeira)}`,
+        );
+      }
 
       await carregarApostas();
     } catch (error) {
@@ -139,6 +171,10 @@ function App() {
         const resultados = await tauri.carregarUltimosResultados(ultimo, 36);
         console.log("[App] Resultados sincronizados:", resultados.length);
 
+        // Concursos das apostas que ficaram para trás (ex.: app fechado por semanas)
+        const resumo = await tauri.sincronizarResultados();
+        console.log("[App] Concursos das apostas recuperados:", resumo.novos);
+
         if (Array.isArray(resultados) && resultados.length > 0) {
           setUltimosResultados(resultados.slice(0, 5));
           if (showSplash) setLastResultado(resultados[0]);
@@ -180,10 +216,38 @@ function App() {
       setShowSettings(true);
     });
 
+    // Eventos do backend
+    const unlistenInicio = listen<InicioSincronizacao>(
+      "sync-started",
+      ({ payload }) => {
+        if (payload.total > 0)
+          toast.loading(`Sincronizando ${payload.total} concurso(s)...`, {
+            id: "sincronizando",
+          });
+      },
+    );
+    const unlistenProgresso = listen<ProgressoSincronizacao>(
+      "sync-progress",
+      ({ payload }) => {
+        if (payload.done >= payload.total) {
+          toast.dismiss("sincronizando");
+          return;
+        }
+        toast.loading(
+          `Sincronizando concurso ${payload.concurso} (${payload.done}/${payload.total})...`,
+          { id: "sincronizando" },
+        );
+      },
+    );
+    const unlistenAposta = listen("aposta-alterada", () => carregarApostas());
+
     return () => {
       clearInterval(interval);
       unlistenShow.then((f) => f());
       unlistenView.then((f) => f());
+      unlistenInicio.then((f) => f());
+      unlistenProgresso.then((f) => f());
+      unlistenAposta.then((f) => f());
     };
   }, []);
 
@@ -19,7 +19,7 @@ export function CardAposta({ aposta, onExcluida }: CardApostaProps) {
     try {
       await excluirAposta(Number(aposta.id));
       console.log(`[SUCCESS] Aposta #${aposta.id} excluída`);
-      toast.success('Aposta removida!');
+      toast.success('Aposta movida para a lixeira!');
       onExcluida();
     } catch (error: any) {
       console.error(`[ERROR] Falha ao excluir aposta #${aposta.id}:`, error);
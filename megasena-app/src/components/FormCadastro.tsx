@@ -20,7 +20,7 @@ import { useState, useEffect } from 'react';
 import { toast } from 'react-hot-toast';
 import { NumeroEsfera } from './NumeroEsfera';
 import { GridNumeros } from './GridNumeros';
-import { adicionarAposta, obterUltimoConcurso } from '../services/tauri';
+import { adicionarAposta, obterProximoConcurso } from '../services/tauri';
 
 interface FormCadastroProps {
   onApostaAdicionada: () => void;
@@ -33,19 +33,20 @@ export function FormCadastro({ onApostaAdicionada }: FormCadastroProps) {
   const [loading, setLoading] = useState(false);
 
   useEffect(() => {
-    const carregarUltimo = async () => {
+    const carregarProximo = async () => {
       try {
-        const ultimo = await obterUltimoConcurso();
-        // Sugerir o último sorteado como padrão (ou o próximo se preferir, mas o último é melhor para conferência)
-        setConcurso(ultimo.toString());
+        // Uma aposta nova concorre a partir do próximo sorteio
+        const proximo = await obterProximoConcurso();
+        setConcurso(proximo.concurso.toString());
       } catch (error) {
-        console.warn('Falha ao obter último concurso:', error);
+        console.warn('Falha ao obter próximo concurso:', error);
       }
     };
-    carregarUltimo();
+    carregarProximo();
   }, []);
 
-  const isValido = selecionados.length >= 6 && selecionados.length <= 20 && concurso !== '';
+  // Concurso em branco: o backend usa o próximo sorteio
+  const isValido = selecionados.length >= 6 && selecionados.length <= 20;
 
   const handleSubmit = async (e: React.FormEvent) => {
     e.preventDefault();
@@ -53,8 +54,7 @@ export function FormCadastro({ onApostaAdicionada }: FormCadastroProps) {
     
     if (!isValido) {
       console.warn('[FORM] Validação falhou:', { 
-        numSelecionados: selecionados.length, 
-        concursoValido: concurso !== '' 
+        numSelecionados: selecionados.length 
       });
       return;
     }
@@ -62,7 +62,11 @@ export function FormCadastro({ onApostaAdicionada }: FormCadastroProps) {
     setLoading(true);
     try {
       console.log('[FORM] Chamando adicionarAposta...');
-      const novaAposta = await adicionarAposta(selecionados, parseInt(concurso), teimosinha);
+      const novaAposta = await adicionarAposta(
+        selecionados,
+        concurso === '' ? undefined : parseInt(concurso),
+        teimosinha
+      );
       console.log('[FORM] Aposta adicionada com sucesso:', novaAposta);
       // Reset form
       setSelecionados([]);
@@ -91,9 +95,8 @@ export function FormCadastro({ onApostaAdicionada }: FormCadastroProps) {
           type="number"
           value={concurso}
           onChange={(e) => setConcurso(e.target.value)}
-          placeholder="2650"
+          placeholder="Próximo concurso"
           className="w-full px-5 py-3 bg-muted border-none rounded-2xl focus:ring-2 focus:ring-green-sphere text-sm font-bold placeholder-muted-foreground/50 transition-all text-foreground"
-          required
         />
       </div>
 
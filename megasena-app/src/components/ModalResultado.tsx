@@ -58,6 +58,17 @@ export function ModalResultado({ resultado, onClose }: ModalResultadoProps) {
               </div>
             )}
 
+            {resultado.faixas?.some(f => f.faixa > 1) && (
+              <div className="space-y-1">
+                {resultado.faixas.filter(f => f.faixa > 1).map(f => (
+                  <div key={f.faixa} className="flex justify-between text-xs font-bold text-muted-foreground uppercase tracking-wide">
+                    <span>{f.acertos === 5 ? 'Quina' : 'Quadra'} · {f.ganhadores} {f.ganhadores === 1 ? 'ganhador' : 'ganhadores'}</span>
+                    <span className="text-foreground">{formatCurreny(f.valorPremio)}</span>
+                  </div>
+                ))}
+              </div>
+            )}
+
             <div className="pt-4">
               <button
                 onClick={onClose}
@@ -17,7 +17,20 @@
  */
 
 import * as tauriCore from '@tauri-apps/api/core';
-import { Aposta, Resultado } from '../types';
+import {
+  Aposta,
+  DiagnosticoProvedor,
+  FiltroApostas,
+  PreferenciasNotificacao,
+  PremioAposta,
+  ProximoConcurso,
+  Resultado,
+  ResumoImportacao,
+  MegaSenaError,
+  ResumoReconciliacao,
+  ResumoSincronizacao,
+  VerificacaoResultado,
+} from '../types';
 
 // Wrapper Robusto para comunicação com o backend Tauri (Rust)
 const invoke = async (...args: any[]): Promise<any> => {
@@ -43,9 +56,21 @@ const invoke = async (...args: any[]): Promise<any> => {
   }
 };
 
+/** Indica se o valor rejeitado por um comando é um `MegaSenaError` */
+export function ehMegaSenaError(e: unknown): e is MegaSenaError {
+  return typeof e === 'object' && e !== null && 'code' in e && 'message' in e;
+}
+
+/** Mensagem legível para qualquer erro vindo do backend */
+export function mensagemErro(e: unknown): string {
+  if (ehMegaSenaError(e) || e instanceof Error) return e.message;
+  return String(e);
+}
+
+/** Sem `concursoInicial`, a aposta começa no próximo concurso. */
 export async function adicionarAposta(
   numeros: number[],
-  concursoInicial: number,
+  concursoInicial: number | undefined,
   quantidadeConcursos: number
 ): Promise<Aposta> {
   return await invoke('adicionar_aposta', {
@@ -55,13 +80,61 @@ export async function adicionarAposta(
   });
 }
 
-export async function listarApostas(): Promise<Aposta[]> {
-  return await invoke('listar_apostas');
+export async function atualizarAposta(
+  id: number,
+  numeros: number[],
+  concursoInicial: number,
+  quantidadeConcursos: number
+): Promise<Aposta> {
+  return await invoke('atualizar_aposta', {
+    id,
+    numeros,
+    concursoInicial,
+    quantidadeConcursos,
+  });
+}
+
+export async function listarApostas(filtro?: FiltroApostas): Promise<Aposta[]> {
+  return await invoke('listar_apostas', { filtro });
 }
 export async function excluirAposta(id: number): Promise<void> {
   return await invoke('excluir_aposta', { id });
 }
 
+export async function arquivarAposta(id: number): Promise<void> {
+  return await invoke('arquivar_aposta', { id });
+}
+
+export async function desarquivarAposta(id: number): Promise<void> {
+  return await invoke('desarquivar_aposta', { id });
+}
+
+export async function restaurarAposta(id: number): Promise<void> {
+  return await invoke('restaurar_aposta', { id });
+}
+
+export async function silenciarAposta(id: number, silenciada: boolean): Promise<void> {
+  return await invoke('silenciar_aposta', { id, silenciada });
+}
+
+export async function obterRetencaoLixeira(): Promise<number> {
+  return await invoke('obter_retencao_lixeira');
+}
+
+export async function definirRetencaoLixeira(dias: number): Promise<void> {
+  return await invoke('definir_retencao_lixeira', { dias });
+}
+
+export async function obterPreferenciasNotificacao(): Promise<PreferenciasNotificacao> {
+  return await invoke('obter_preferencias_notificacao');
+}
+
+export async function definirPreferenciasNotificacao(
+  preferencias: PreferenciasNotificacao,
+): Promise<void> {
+  return await invoke('definir_preferencias_notificacao', { preferencias });
+}
+
 
 
 export async function verificarResultados(concurso: number): Promise<Resultado> {
@@ -81,3 +154,48 @@ export async function carregarUltimosResultados(
 export async function obterUltimoConcurso(): Promise<number> {
   return await invoke('obter_ultimo_concurso');
 }
+
+export async function obterProximoConcurso(): Promise<ProximoConcurso> {
+  return await invoke('obter_proximo_concurso');
+}
+
+export async function listarVerificacoes(somenteConflitos = false): Promise<VerificacaoResultado[]> {
+  return await invoke('listar_verificacoes', { somenteConflitos });
+}
+
+export async function reconciliarResultados(): Promise<ResumoReconciliacao> {
+  return await invoke('reconciliar_resultados');
+}
+
+export async function diagnosticoProvedores(): Promise<DiagnosticoProvedor[]> {
+  return await invoke('diagnostico_provedores');
+}
+
+export async function obterPremiosAposta(apostaId: number): Promise<PremioAposta[]> {
+  return await invoke('obter_premios_aposta', { apostaId });
+}
+
+/** Importa o histórico a partir da planilha da Caixa (XLSX/HTML) ou CSV.
+ *  O progresso chega pelo evento `importacao-progresso`. */
+export async function importarResultados(caminho: string): Promise<ResumoImportacao> {
+  return await invoke('importar_resultados', { caminho });
+}
+
+export async function reprocessarResultados(): Promise<ResumoImportacao> {
+  return await invoke('reprocessar_resultados');
+}
+
+/** Próxima verificação em segundo plano (ISO 8601), ou `null` se uma está em andamento */
+export async function obterProximaVerificacao(): Promise<string | null> {
+  return await invoke('obter_proxima_verificacao');
+}
+
+/** Antecipa a verificação em segundo plano; novos resultados chegam pelo evento `novo-resultado`. */
+export async function verificarAgora(): Promise<void> {
+  return await invoke('verificar_agora');
+}
+
+/** Busca os concursos já sorteados que faltam para as apostas e processa os acertos. */
+export async function sincronizarResultados(): Promise<ResumoSincronizacao> {
+  return await invoke('sincronizar_resultados');
+}
@@ -18,6 +18,8 @@
 
 // TypeScript Types for MegaSena App
 
+export type FiltroApostas = 'ativas' | 'arquivadas' | 'finalizadas' | 'lixeira' | 'todas';
+
 export interface Aposta {
   id: number;
   numeros: number[];
@@ -25,10 +27,19 @@ export interface Aposta {
   quantidadeConcursos: number;
   dataCriacao: string;
   ativa: boolean;
+  excluidaEm?: string;
+  silenciada: boolean;
   acertos: { [concurso: number]: number };
+  premiacoes: { [concurso: number]: ContagemFaixas };
   resultadosConcursos: { [concurso: number]: number[] };
 }
 
+export interface ContagemFaixas {
+  senas: number;
+  quinas: number;
+  quadras: number;
+}
+
 export interface Resultado {
   concurso: number;
   numerosSorteados: number[];
@@ -37,6 +48,17 @@ export interface Resultado {
   valorPremio?: number;
   ganhadores?: number;
   valorTotal?: number;
+  faixas: FaixaPremio[];
+  fonte?: string;
+  fetchedAt?: string;
+  provisional: boolean;
+}
+
+export interface FaixaPremio {
+  faixa: number;
+  acertos: number;
+  ganhadores: number;
+  valorPremio: number;
 }
 
 export interface ApostaResultado {
@@ -45,6 +67,124 @@ export interface ApostaResultado {
   acertos: number;
 }
 
+export interface PremioAposta {
+  apostaId: number;
+  concurso: number;
+  faixa: number;
+  acertos: number;
+  quantidade: number;
+  valorUnitario: number;
+  valorTotal: number;
+}
+
 export interface ApostaComResultados extends Aposta {
   resultados?: Map<number, ApostaResultado>;
 }
+
+export interface ResumoImportacao {
+  total: number;
+  novos: number;
+  atualizados: number;
+  inalterados: number;
+  linhasIgnoradas: number;
+}
+
+export type EstadoCircuito = 'fechado' | 'aberto' | 'meioAberto';
+
+export interface DiagnosticoProvedor {
+  nome: string;
+  prioridade: number;
+  estado: EstadoCircuito;
+  falhasConsecutivas: number;
+  totalSucessos: number;
+  totalFalhas: number;
+  ultimoErro?: string;
+  reabreEmSegundos?: number;
+}
+
+export type StatusVerificacao = 'concordante' | 'conflito' | 'fonteUnica';
+
+export interface VerificacaoResultado {
+  concurso: number;
+  status: StatusVerificacao;
+  provedores: string[];
+  divergencias: string[];
+  verificadoEm?: string;
+}
+
+export interface ProximoConcurso {
+  concurso: number;
+  dataSorteio?: string;
+  valorEstimado?: number;
+  valorAcumulado?: number;
+}
+
+export interface ResumoReconciliacao {
+  verificados: number;
+  confirmados: number;
+  corrigidos: number[];
+  pendentes: number;
+}
+
+/** Código estável dos erros retornados pelos comandos do backend */
+export type CodigoErro =
+  | 'network'
+  | 'providerHttp'
+  | 'parse'
+  | 'notDrawn'
+  | 'validation'
+  | 'conflict'
+  | 'notFound'
+  | 'file'
+  | 'database'
+  | 'lockPoisoned';
+
+/** Erro serializado pelos comandos Tauri */
+export interface MegaSenaError {
+  code: CodigoErro;
+  message: string;
+  details?: Record<string, unknown> | null;
+}
+
+export interface ResumoSincronizacao {
+  ultimoConcurso: number;
+  pendentes: number;
+  novos: number[];
+  conflitos: number[];
+  indisponiveis: number[];
+}
+
+/** Payload de `sync-started` */
+export interface InicioSincronizacao {
+  total: number;
+}
+
+/** Payload de `sync-progress`, enviado a cada concurso concluído */
+export interface ProgressoSincronizacao {
+  done: number;
+  total: number;
+  concurso: number;
+}
+
+/** Payload de `aposta-premiada`: maior faixa atingida (1 = Sena, 2 = Quina, 3 = Quadra) */
+export interface ApostaPremiada {
+  apostaId: number;
+  concurso: number;
+  faixa: number;
+}
+
+/** Payload de `aposta-alterada` */
+export interface ApostaAlterada {
+  apostaId: number;
+}
+
+/** Preferências das notificações do sistema */
+export interface PreferenciasNotificacao {
+  /** Menor faixa que notifica (1 = Sena, 2 = Quina, 3 = Quadra) */
+  faixaMinima: number;
+  /** Horário de silêncio, "HH:MM" no horário local */
+  silencioInicio?: string | null;
+  silencioFim?: string | null;
+  notificarSemPremio: boolean;
+  notificarAcumulado: boolean;
+}